@@ -1,5 +1,7 @@
-use crate::node::{NodeCmd, RenderNode, UpdateNode};
+use crate::node::{Msg, NodeCmd, RenderNode, UpdateNode};
 use std::collections::VecDeque;
+use std::future::{self, Future};
+use std::task::Poll;
 
 mod schedule;
 
@@ -8,14 +10,20 @@ use schedule::Scedule;
 pub struct Runtime<Node: UpdateNode + RenderNode<NodeCmd> + 'static> {
     schedule: Scedule,
     node: Node,
+    is_busy: bool,
 }
 
 impl<Node: UpdateNode + RenderNode<NodeCmd> + 'static> Runtime<Node> {
-    pub async fn run(node: Node) {
-        let mut runtime = Self {
+    pub fn new(node: Node) -> Self {
+        Self {
             schedule: Scedule::new(),
-            node: node,
-        };
+            node,
+            is_busy: true,
+        }
+    }
+
+    pub async fn run(node: Node) {
+        let mut runtime = Self::new(node);
 
         let mut is_busy = true;
         loop {
@@ -23,12 +31,53 @@ impl<Node: UpdateNode + RenderNode<NodeCmd> + 'static> Runtime<Node> {
         }
     }
 
+    /// Renders and updates the node until no message is ready.
+    /// Unlike `run`, this does not wait for pending tasks.
+    /// Returns `true` if the node is rendered or updated.
+    pub async fn run_until_idle(&mut self) -> bool {
+        let mut is_worked = false;
+        loop {
+            if self.is_busy {
+                let mut schedules = self.node.render();
+                self.schedule.append(&mut schedules).await;
+                is_worked = true;
+            }
+
+            let mut listener = Box::pin(self.schedule.listen());
+            let msgs = future::poll_fn(|cx| match listener.as_mut().poll(cx) {
+                Poll::Ready(msgs) => Poll::Ready(msgs),
+                Poll::Pending => Poll::Ready(vec![]),
+            })
+            .await;
+
+            if msgs.is_empty() {
+                self.is_busy = false;
+                return is_worked;
+            }
+
+            self.is_busy = self.update(msgs.into()).await;
+            is_worked = true;
+        }
+    }
+
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    pub fn node_mut(&mut self) -> &mut Node {
+        &mut self.node
+    }
+
     async fn event_loop(&mut self, is_busy: bool) -> bool {
         if is_busy {
             let mut schedules = self.node.render();
             self.schedule.append(&mut schedules).await;
         }
-        let mut msgs: VecDeque<_> = self.schedule.listen().await.into();
+        let msgs: VecDeque<_> = self.schedule.listen().await.into();
+        self.update(msgs).await
+    }
+
+    async fn update(&mut self, mut msgs: VecDeque<Msg>) -> bool {
         let mut schedules = VecDeque::new();
         let mut is_busy = false;
         while let Some(msg) = msgs.pop_front() {
@@ -0,0 +1,31 @@
+//! A site binary which renders pages of `nusa`.
+//!
+//! ```sh
+//! cargo run --example ssg -- --out ./dist
+//! ```
+
+extern crate nusa;
+extern crate nusa_ssg;
+
+use nusa::prelude::*;
+use nusa_ssg::{RouteTable, Site};
+
+fn main() {
+    let route_table = RouteTable::new()
+        .route("/", |_| {
+            vec![Html::h1(
+                Attributes::new(),
+                Events::new(),
+                vec![Html::text("Hello World")],
+            )]
+        })
+        .route("/about", |_| {
+            vec![Html::p(
+                Attributes::new(),
+                Events::new(),
+                vec![Html::text("Rendered by nusa-ssg")],
+            )]
+        });
+
+    Site::new(route_table).main();
+}
@@ -0,0 +1,10 @@
+extern crate kagura;
+extern crate nusa;
+
+pub mod manifest;
+pub mod route_table;
+pub mod site;
+
+pub use manifest::Manifest;
+pub use route_table::RouteTable;
+pub use site::Site;
@@ -0,0 +1,178 @@
+use nusa::util::json_string;
+use std::path::Path;
+
+/// A list of generated files.
+/// A hydrating client reads `manifest.json` to find the page of the current route and the element to mount on.
+pub struct Manifest {
+    pub mount_id: String,
+    pub routes: Vec<ManifestRoute>,
+    pub assets: Vec<String>,
+}
+
+pub struct ManifestRoute {
+    pub path: String,
+    pub file: String,
+}
+
+impl Manifest {
+    pub fn new(mount_id: impl Into<String>) -> Self {
+        Self {
+            mount_id: mount_id.into(),
+            routes: vec![],
+            assets: vec![],
+        }
+    }
+
+    pub fn push_route(&mut self, path: impl Into<String>, file: &Path) {
+        self.routes.push(ManifestRoute {
+            path: path.into(),
+            file: to_url_path(file),
+        });
+    }
+
+    pub fn push_asset(&mut self, file: &Path) {
+        self.assets.push(to_url_path(file));
+    }
+
+    /// Reads `manifest.json` which is written by `to_json`. Unknown fields are ignored.
+    pub fn from_json(json: &str) -> Self {
+        let mut manifest = Self::new("");
+        let mut key = String::new();
+        let mut path = None;
+        let mut chars = json.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '"' {
+                continue;
+            }
+            let text = read_json_string(&mut chars);
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.next_if_eq(&':').is_some() {
+                key = text;
+                continue;
+            }
+            match key.as_str() {
+                "mount_id" => manifest.mount_id = text,
+                "path" => path = Some(text),
+                "file" => {
+                    if let Some(path) = path.take() {
+                        manifest.routes.push(ManifestRoute { path, file: text });
+                    }
+                }
+                "assets" => manifest.assets.push(text),
+                _ => {}
+            }
+        }
+
+        manifest
+    }
+
+    pub fn to_json(&self) -> String {
+        let routes = self
+            .routes
+            .iter()
+            .map(|route| {
+                format!(
+                    "    {{\"path\": {}, \"file\": {}}}",
+                    json_string(&route.path),
+                    json_string(&route.file)
+                )
+            })
+            .collect::<Vec<_>>();
+        let assets = self
+            .assets
+            .iter()
+            .map(|asset| format!("    {}", json_string(asset)))
+            .collect::<Vec<_>>();
+
+        format!(
+            "{{\n  \"mount_id\": {},\n  \"routes\": [{}],\n  \"assets\": [{}]\n}}\n",
+            json_string(&self.mount_id),
+            json_list(routes),
+            json_list(assets)
+        )
+    }
+}
+
+fn to_url_path(file: &Path) -> String {
+    file.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn json_list(items: Vec<String>) -> String {
+    if items.is_empty() {
+        String::new()
+    } else {
+        format!("\n{}\n  ", items.join(",\n"))
+    }
+}
+
+/// Reads a string literal after the opening quote.
+fn read_json_string(chars: &mut impl Iterator<Item = char>) -> String {
+    let mut text = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => text.push('\n'),
+                Some('r') => text.push('\r'),
+                Some('t') => text.push('\t'),
+                Some('b') => text.push('\u{8}'),
+                Some('f') => text.push('\u{c}'),
+                Some('u') => {
+                    let code = chars.by_ref().take(4).collect::<String>();
+                    if let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                        text.push(c);
+                    }
+                }
+                Some(c) => text.push(c),
+                None => break,
+            },
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_writes_and_reads_json() {
+        let mut manifest = Manifest::new("app");
+        manifest.push_route("/", Path::new("index.html"));
+        manifest.push_route("/docs/\"a\"", &Path::new("docs").join("\"a\".html"));
+        manifest.push_asset(&Path::new("img").join("logo.png"));
+
+        let json = manifest.to_json();
+        assert_eq!(
+            json,
+            concat!(
+                "{\n",
+                "  \"mount_id\": \"app\",\n",
+                "  \"routes\": [\n",
+                "    {\"path\": \"/\", \"file\": \"index.html\"},\n",
+                "    {\"path\": \"/docs/\\\"a\\\"\", \"file\": \"docs/\\\"a\\\".html\"}\n",
+                "  ],\n",
+                "  \"assets\": [\n",
+                "    \"img/logo.png\"\n",
+                "  ]\n",
+                "}\n"
+            )
+        );
+
+        let read = Manifest::from_json(&json);
+        assert_eq!(read.mount_id, "app");
+        assert_eq!(
+            read.routes
+                .iter()
+                .map(|route| (route.path.as_str(), route.file.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("/", "index.html"), ("/docs/\"a\"", "docs/\"a\".html")]
+        );
+        assert_eq!(read.assets, vec!["img/logo.png"]);
+    }
+}
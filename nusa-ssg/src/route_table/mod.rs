@@ -0,0 +1,142 @@
+use kagura::node::RenderNode;
+use nusa::dom_node::basic_dom_node::BasicDomComponent;
+use nusa::html_component::Sub;
+use nusa::string_node::BasicStringNode;
+use nusa::{Html, HtmlComponent};
+use std::ffi::OsStr;
+use std::io;
+use std::path::PathBuf;
+
+pub struct RouteTable {
+    routes: Vec<Route>,
+}
+
+pub struct Route {
+    path: String,
+    node: BasicStringNode,
+}
+
+impl RouteTable {
+    pub fn new() -> Self {
+        Self { routes: vec![] }
+    }
+
+    /// Adds a route which is rendered by `render`, like `BasicDomNode::new`.
+    pub fn route(
+        mut self,
+        path: impl Into<String>,
+        render: impl FnMut(&BasicDomComponent) -> Vec<Html> + 'static,
+    ) -> Self {
+        self.routes.push(Route {
+            path: path.into(),
+            node: BasicStringNode::new(render),
+        });
+        self
+    }
+
+    /// Adds a route whose root is a component `C` constructed with `props`.
+    pub fn component<C: HtmlComponent>(
+        self,
+        path: impl Into<String>,
+        mut props: impl FnMut() -> C::Props + 'static,
+    ) -> Self {
+        self.route(path, move |this| {
+            vec![C::empty(this, None, props(), Sub::none())]
+        })
+    }
+
+    pub fn routes(&self) -> &Vec<Route> {
+        &self.routes
+    }
+
+    pub fn routes_mut(&mut self) -> &mut Vec<Route> {
+        &mut self.routes
+    }
+}
+
+impl std::default::Default for RouteTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Route {
+    pub fn path(&self) -> &String {
+        &self.path
+    }
+
+    /// Path of the output file relative to the output directory.
+    ///
+    /// - `/` and `/docs/` are rendered to `index.html` and `docs/index.html`
+    /// - `/docs/intro` is rendered to `docs/intro.html`
+    /// - `/404.html` is rendered to `404.html`
+    pub fn file_path(&self) -> io::Result<PathBuf> {
+        let mut file_path = PathBuf::new();
+        let segments = self
+            .path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+
+        for segment in &segments {
+            if *segment == "." || *segment == ".." || segment.contains('\\') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid route path: {}", self.path),
+                ));
+            }
+            file_path.push(segment);
+        }
+
+        if segments.is_empty() || self.path.ends_with('/') {
+            file_path.push("index.html");
+        } else if file_path.extension() != Some(OsStr::new("html")) {
+            let mut file_name = file_path.file_name().unwrap_or_default().to_os_string();
+            file_name.push(".html");
+            file_path.set_file_name(file_name);
+        }
+
+        Ok(file_path)
+    }
+
+    pub fn render(&mut self) -> String {
+        self.node.render()
+    }
+
+    /// Stylesheets of the rendered page as `<style>` elements.
+    pub fn styles(&self) -> String {
+        self.node.styles()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn file_path(path: &str) -> io::Result<PathBuf> {
+        RouteTable::new().route(path, |_| vec![]).routes()[0].file_path()
+    }
+
+    #[test]
+    fn it_maps_routes_to_files() {
+        assert_eq!(file_path("/").unwrap(), Path::new("index.html"));
+        assert_eq!(
+            file_path("/docs/").unwrap(),
+            Path::new("docs").join("index.html")
+        );
+        assert_eq!(
+            file_path("/docs/intro").unwrap(),
+            Path::new("docs").join("intro.html")
+        );
+        assert_eq!(file_path("/404.html").unwrap(), Path::new("404.html"));
+        assert_eq!(file_path("/v1.2").unwrap(), Path::new("v1.2.html"));
+    }
+
+    #[test]
+    fn it_rejects_paths_out_of_the_output_directory() {
+        assert!(file_path("/../etc/passwd").is_err());
+        assert!(file_path("/docs/./intro").is_err());
+        assert!(file_path("/a\\b").is_err());
+    }
+}
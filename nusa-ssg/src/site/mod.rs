@@ -0,0 +1,396 @@
+use crate::manifest::Manifest;
+use crate::route_table::RouteTable;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const DEFAULT_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+{{styles}}
+</head>
+<body>
+<div id="{{mount_id}}">{{body}}</div>
+</body>
+</html>
+"#;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+pub struct Site {
+    route_table: RouteTable,
+    out_dir: PathBuf,
+    asset_dirs: Vec<PathBuf>,
+    template: String,
+    mount_id: String,
+    dependencies: Vec<PathBuf>,
+    force: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    pub rendered: Vec<PathBuf>,
+    pub skipped: Vec<PathBuf>,
+    pub copied: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl Site {
+    pub fn new(route_table: RouteTable) -> Self {
+        Self {
+            route_table,
+            out_dir: PathBuf::from("dist"),
+            asset_dirs: vec![],
+            template: String::from(DEFAULT_TEMPLATE),
+            mount_id: String::from("app"),
+            dependencies: vec![],
+            force: false,
+        }
+    }
+
+    pub fn out_dir(mut self, out_dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = out_dir.into();
+        self
+    }
+
+    /// Adds a directory whose files are copied into the output directory.
+    pub fn assets(mut self, asset_dir: impl Into<PathBuf>) -> Self {
+        self.asset_dirs.push(asset_dir.into());
+        self
+    }
+
+    /// Sets the page template.
+    /// `{{body}}` is replaced with rendered HTML, `{{styles}}` is replaced with stylesheets of the page
+    /// and `{{mount_id}}` is replaced with the id of the root element.
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Loads the page template from a file. The file also becomes a dependency of every page.
+    pub fn template_file(mut self, template_file: impl Into<PathBuf>) -> io::Result<Self> {
+        let template_file = template_file.into();
+        self.template = fs::read_to_string(&template_file)?;
+        self.dependencies.push(template_file);
+        Ok(self)
+    }
+
+    pub fn mount_id(mut self, mount_id: impl Into<String>) -> Self {
+        self.mount_id = mount_id.into();
+        self
+    }
+
+    /// Adds a file which pages depend on.
+    /// Pages are rendered again when the file is newer than them.
+    pub fn dependency(mut self, dependency: impl Into<PathBuf>) -> Self {
+        self.dependencies.push(dependency.into());
+        self
+    }
+
+    /// Renders every page even if it is up to date.
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Renders pages and copies assets.
+    /// Pages which are listed in the previous manifest but not in the route table are removed.
+    pub fn build(&mut self) -> io::Result<BuildReport> {
+        let mut report = BuildReport::default();
+        let mut manifest = Manifest::new(self.mount_id.as_str());
+        let modified = self.dependencies_modified();
+        let prev_manifest = fs::read_to_string(self.out_dir.join(MANIFEST_FILE))
+            .ok()
+            .map(|json| Manifest::from_json(&json));
+
+        fs::create_dir_all(&self.out_dir)?;
+
+        for route in self.route_table.routes_mut() {
+            let file_path = route.file_path()?;
+            let out_path = self.out_dir.join(&file_path);
+
+            if self.force || !is_up_to_date(&out_path, modified) {
+                let body = route.render();
+                let page = self
+                    .template
+                    .replace("{{mount_id}}", &self.mount_id)
+                    .replace("{{styles}}", &route.styles())
+                    .replace("{{body}}", &body);
+                if let Some(parent) = out_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&out_path, page)?;
+                report.rendered.push(out_path);
+            } else {
+                report.skipped.push(out_path);
+            }
+
+            manifest.push_route(route.path().as_str(), &file_path);
+        }
+
+        if let Some(prev_manifest) = prev_manifest {
+            for prev_route in &prev_manifest.routes {
+                let is_kept = manifest
+                    .routes
+                    .iter()
+                    .any(|route| route.file == prev_route.file);
+                if !is_kept {
+                    if let Some(removed) = self.remove_page(&prev_route.file)? {
+                        report.removed.push(removed);
+                    }
+                }
+            }
+        }
+
+        for asset_dir in &self.asset_dirs {
+            for file_path in list_files(asset_dir)? {
+                let src_path = asset_dir.join(&file_path);
+                let out_path = self.out_dir.join(&file_path);
+                let src_modified = fs::metadata(&src_path).and_then(|m| m.modified()).ok();
+
+                if self.force || !is_up_to_date(&out_path, src_modified) {
+                    if let Some(parent) = out_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::copy(&src_path, &out_path)?;
+                    report.copied.push(out_path);
+                } else {
+                    report.skipped.push(out_path);
+                }
+
+                manifest.push_asset(&file_path);
+            }
+        }
+
+        fs::write(self.out_dir.join(MANIFEST_FILE), manifest.to_json())?;
+
+        Ok(report)
+    }
+
+    /// Builds the site with command line arguments, and exits on failure.
+    /// Call this from `main` of your site binary.
+    ///
+    /// ```text
+    /// nusa-ssg [--out <dir>] [--assets <dir>]... [--force]
+    /// ```
+    pub fn main(mut self) {
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--out" => match args.next() {
+                    Some(out_dir) => self = self.out_dir(out_dir),
+                    None => exit_with_usage("--out needs a directory"),
+                },
+                "--assets" => match args.next() {
+                    Some(asset_dir) => self = self.assets(asset_dir),
+                    None => exit_with_usage("--assets needs a directory"),
+                },
+                "--force" => self = self.force(true),
+                "--help" => {
+                    println!("{}", USAGE);
+                    return;
+                }
+                arg => exit_with_usage(&format!("unknown argument: {}", arg)),
+            }
+        }
+
+        match self.build() {
+            Ok(report) => println!(
+                "rendered {} pages, copied {} assets, removed {} pages, skipped {} up-to-date files",
+                report.rendered.len(),
+                report.copied.len(),
+                report.removed.len(),
+                report.skipped.len()
+            ),
+            Err(err) => {
+                eprintln!("nusa-ssg: {}", err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    /// Removes a page of the previous build, and its directories which become empty.
+    /// Files out of the output directory are never removed.
+    fn remove_page(&self, file: &str) -> io::Result<Option<PathBuf>> {
+        let segments = file.split('/').collect::<Vec<_>>();
+        let is_valid = segments.iter().all(|segment| {
+            !segment.is_empty() && *segment != "." && *segment != ".." && !segment.contains('\\')
+        });
+        if !is_valid {
+            return Ok(None);
+        }
+
+        let out_path = segments
+            .iter()
+            .fold(self.out_dir.clone(), |path, segment| path.join(segment));
+        match fs::remove_file(&out_path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        }
+
+        let mut dir = out_path.parent();
+        while let Some(now) = dir.filter(|dir| *dir != self.out_dir) {
+            if fs::remove_dir(now).is_err() {
+                break;
+            }
+            dir = now.parent();
+        }
+
+        Ok(Some(out_path))
+    }
+
+    /// The newest modification time of the running binary and the dependencies.
+    /// `None` means that it is unknown, so every page must be rendered.
+    fn dependencies_modified(&self) -> Option<SystemTime> {
+        let mut modified = std::env::current_exe()
+            .and_then(fs::metadata)
+            .and_then(|metadata| metadata.modified())
+            .ok()?;
+
+        for dependency in &self.dependencies {
+            let dependency_modified = fs::metadata(dependency)
+                .and_then(|metadata| metadata.modified())
+                .ok()?;
+            if dependency_modified > modified {
+                modified = dependency_modified;
+            }
+        }
+
+        Some(modified)
+    }
+}
+
+const USAGE: &str = "usage: nusa-ssg [--out <dir>] [--assets <dir>]... [--force]";
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("nusa-ssg: {}\n{}", message, USAGE);
+    std::process::exit(2);
+}
+
+fn is_up_to_date(out_path: &Path, src_modified: Option<SystemTime>) -> bool {
+    let out_modified = fs::metadata(out_path).and_then(|metadata| metadata.modified());
+    match (out_modified, src_modified) {
+        (Ok(out_modified), Some(src_modified)) => out_modified >= src_modified,
+        _ => false,
+    }
+}
+
+/// Lists files in `dir` recursively as relative paths.
+fn list_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    let mut dirs = vec![PathBuf::new()];
+
+    while let Some(relative_dir) = dirs.pop() {
+        let mut entries = fs::read_dir(dir.join(&relative_dir))?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let relative_path = relative_dir.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                dirs.push(relative_path);
+            } else {
+                files.push(relative_path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kagura::component::{Constructor, Render, Update};
+    use kagura::Component;
+    use nusa::prelude::*;
+    use nusa::style::{self, Style};
+    use std::time::Duration;
+
+    struct Card;
+
+    impl Component for Card {
+        type Props = ();
+        type Msg = ();
+        type Event = ();
+    }
+
+    impl Constructor for Card {
+        fn constructor(_props: ()) -> Self {
+            Self
+        }
+    }
+
+    impl Update for Card {}
+
+    impl Render<Html> for Card {
+        type Children = ();
+        fn render(&self, _children: ()) -> Html {
+            Html::div(
+                Attributes::new().class(style::class_name::<Self>("card")),
+                Events::new(),
+                vec![],
+            )
+        }
+    }
+
+    impl HtmlComponent for Card {
+        fn stylesheet() -> Option<Style> {
+            Some(Style::new().rule(".card", &[("color", "red")]))
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nusa-ssg-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn it_compares_modification_times() {
+        let dir = temp_dir("up-to-date");
+        fs::create_dir_all(&dir).unwrap();
+        let out_path = dir.join("index.html");
+        assert!(!is_up_to_date(&out_path, Some(SystemTime::UNIX_EPOCH)));
+
+        fs::write(&out_path, "").unwrap();
+        let out_modified = fs::metadata(&out_path).unwrap().modified().unwrap();
+        assert!(is_up_to_date(&out_path, Some(out_modified)));
+        assert!(!is_up_to_date(
+            &out_path,
+            Some(out_modified + Duration::from_secs(1))
+        ));
+        assert!(!is_up_to_date(&out_path, None));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn it_inlines_styles_and_removes_stale_pages() {
+        let dir = temp_dir("build");
+        let route_table = RouteTable::new()
+            .component::<Card>("/", || ())
+            .route("/docs/intro", |_| vec![Html::text("intro")]);
+        let report = Site::new(route_table).out_dir(&dir).build().unwrap();
+        assert_eq!(report.rendered.len(), 2);
+
+        let index = fs::read_to_string(dir.join("index.html")).unwrap();
+        let scope = style::scope::<Card>();
+        assert!(index.contains(&format!(
+            r#"<style data-nusa-style="{0}">.{0}-card{{color:red;}}</style>"#,
+            scope
+        )));
+        let intro = fs::read_to_string(dir.join("docs").join("intro.html")).unwrap();
+        assert!(!intro.contains("<style"));
+
+        let route_table = RouteTable::new().component::<Card>("/", || ());
+        let report = Site::new(route_table).out_dir(&dir).build().unwrap();
+        assert_eq!(report.removed, vec![dir.join("docs").join("intro.html")]);
+        assert!(!dir.join("docs").exists());
+        assert!(dir.join("index.html").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
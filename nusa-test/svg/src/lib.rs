@@ -14,22 +14,19 @@ use wasm_bindgen::prelude::*;
 pub fn main() {
     wasm_bindgen_futures::spawn_local(async {
         kagura::Runtime::run(nusa::dom_node::BasicDomNode::new(entry_point(), |_| {
-            vec![Html::element(
-                "svg",
+            vec![Html::svg(
                 Attributes::new()
-                    .string("width", "400")
-                    .string("height", "200")
-                    .string("viewBox", "0 0 400 200")
-                    .string("xmlns", "http://www.w3.org/2000/svg"),
+                    .width(400.0)
+                    .height(200.0)
+                    .view_box(0.0, 0.0, 400.0, 200.0),
                 Events::new(),
-                vec![Html::element(
-                    "rect",
+                vec![Html::svg_rect(
                     Attributes::new()
-                        .num("x", 10.0)
-                        .num("y", 10.0)
-                        .num("width", 380.0)
-                        .num("height", 180.0)
-                        .string("fill", "#e74c3c"),
+                        .x(10.0)
+                        .y(10.0)
+                        .width(380.0)
+                        .height(180.0)
+                        .fill("#e74c3c"),
                     Events::new(),
                     vec![],
                 )],
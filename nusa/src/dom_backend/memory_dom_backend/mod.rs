@@ -0,0 +1,894 @@
+use super::{DomBackend, DomEventListener, DomEventListenerOptions};
+use crate::string_renderer::{escape_attribute, escape_text, VOID_ELEMENTS};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::convert::IntoWasmAbi;
+use wasm_bindgen::{JsCast, JsValue};
+
+mod selector;
+
+use selector::Selector;
+
+/// An in-memory fake DOM.
+/// It works without browser, so `DomRenderer` can be tested natively.
+/// Every operation is recorded as `Mutation`.
+#[derive(Clone)]
+pub struct MemoryDomBackend {
+    mutations: Rc<RefCell<Vec<Mutation>>>,
+    event_listeners: Rc<RefCell<Vec<MemoryEventListener>>>,
+    listener_count: Rc<Cell<usize>>,
+    dispatches: Rc<RefCell<Vec<MemoryDispatch>>>,
+}
+
+#[derive(Clone)]
+pub struct MemoryNode {
+    data: Rc<RefCell<MemoryNodeData>>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Mutation {
+    CreateElement {
+        tag_name: String,
+        namespace: Option<String>,
+    },
+    CreateTextNode {
+        text: String,
+    },
+    InsertBefore,
+    ReplaceChild,
+    RemoveChild,
+    SetText {
+        text: String,
+    },
+    SetAttribute {
+        name: String,
+        value: String,
+    },
+    RemoveAttribute {
+        name: String,
+    },
+    SetAttributeNs {
+        namespace: String,
+        name: String,
+        value: String,
+    },
+    RemoveAttributeNs {
+        namespace: String,
+        name: String,
+    },
+    AddClass {
+        name: String,
+    },
+    RemoveClass {
+        name: String,
+    },
+    SetStyleProperty {
+        name: String,
+        value: String,
+    },
+    RemoveStyleProperty {
+        name: String,
+    },
+    SetValue {
+        value: String,
+    },
+    SetProperty {
+        name: String,
+    },
+    Focus,
+    AddEventListener {
+        event_type: String,
+        options: DomEventListenerOptions,
+    },
+    RemoveEventListener {
+        event_type: String,
+        capture: bool,
+    },
+}
+
+pub struct MemoryEventListener {
+    pub id: usize,
+    pub target: MemoryNode,
+    pub event_type: String,
+    pub options: DomEventListenerOptions,
+    pub listener: Rc<DomEventListener>,
+}
+
+/// An event which is dispatched by `MemoryDomBackend::dispatch_event` now.
+struct MemoryDispatch {
+    target: MemoryNode,
+    key: Option<String>,
+    default_prevented: bool,
+    propagation_stopped: bool,
+}
+
+struct MemoryNodeData {
+    kind: MemoryNodeKind,
+    parent: Weak<RefCell<MemoryNodeData>>,
+    children: Vec<MemoryNode>,
+}
+
+enum MemoryNodeKind {
+    Element {
+        tag_name: String,
+        namespace: Option<String>,
+        attributes: Vec<(String, String)>,
+        value: Option<String>,
+        // `checked` of `input` and `selected` of `option`. `None` follows the attribute.
+        checked: Option<bool>,
+        properties: Vec<(String, Rc<JsValue>)>,
+    },
+    Text(String),
+    RNode,
+}
+
+impl MemoryDomBackend {
+    pub fn new() -> Self {
+        Self {
+            mutations: Rc::new(RefCell::new(vec![])),
+            event_listeners: Rc::new(RefCell::new(vec![])),
+            listener_count: Rc::new(Cell::new(0)),
+            dispatches: Rc::new(RefCell::new(vec![])),
+        }
+    }
+
+    /// Creates a detached `div` to mount on. It is not recorded as `Mutation`.
+    pub fn create_root(&self) -> MemoryNode {
+        MemoryNode::new(MemoryNodeKind::Element {
+            tag_name: String::from("div"),
+            namespace: None,
+            attributes: vec![],
+            value: None,
+            checked: None,
+            properties: vec![],
+        })
+    }
+
+    pub fn mutations(&self) -> Vec<Mutation> {
+        self.mutations.borrow().clone()
+    }
+
+    pub fn clear_mutations(&self) {
+        self.mutations.borrow_mut().clear();
+    }
+
+    pub fn count_mutations(&self, f: impl FnMut(&&Mutation) -> bool) -> usize {
+        self.mutations.borrow().iter().filter(f).count()
+    }
+
+    pub fn event_listeners(&self) -> std::cell::Ref<'_, Vec<MemoryEventListener>> {
+        self.event_listeners.borrow()
+    }
+
+    /// Dispatches a synthetic event to `target` like `dispatchEvent`.
+    /// Listeners are called in capture, target and bubble phase.
+    /// Returns `false` if a handler cancels the event.
+    ///
+    /// Handlers read the event through `DomBackend`, like `VEvent::event_target`.
+    /// Data of specific interfaces, like `clientX` of `MouseEvent`, can not be read without browser.
+    pub fn dispatch_event(&self, target: &MemoryNode, event_type: &str, bubbles: bool) -> bool {
+        self.dispatch(target, event_type, bubbles, None)
+    }
+
+    /// Dispatches a bubbling keyboard event, whose `key` is `key`.
+    pub fn dispatch_key_event(&self, target: &MemoryNode, event_type: &str, key: &str) -> bool {
+        self.dispatch(target, event_type, true, Some(String::from(key)))
+    }
+
+    fn dispatch(
+        &self,
+        target: &MemoryNode,
+        event_type: &str,
+        bubbles: bool,
+        key: Option<String>,
+    ) -> bool {
+        let mut path = vec![target.clone()];
+        while let Some(parent) = path.last().and_then(|node| node.parent()) {
+            path.push(parent);
+        }
+
+        let mut phases = vec![];
+        for node in path.iter().skip(1).rev() {
+            phases.push((node, true));
+        }
+        phases.push((target, true));
+        phases.push((target, false));
+        if bubbles {
+            for node in path.iter().skip(1) {
+                phases.push((node, false));
+            }
+        }
+
+        self.dispatches.borrow_mut().push(MemoryDispatch {
+            target: target.clone(),
+            key,
+            default_prevented: false,
+            propagation_stopped: false,
+        });
+
+        for (node, capture) in phases {
+            self.call_listeners(node, event_type, capture);
+            let propagation_stopped = self
+                .dispatches
+                .borrow()
+                .last()
+                .is_some_and(|dispatch| dispatch.propagation_stopped);
+            if propagation_stopped {
+                break;
+            }
+        }
+
+        let dispatch = self.dispatches.borrow_mut().pop();
+        !dispatch.is_some_and(|dispatch| dispatch.default_prevented)
+    }
+
+    /// Calls listeners which are registered when the node is reached, like browsers do.
+    fn call_listeners(&self, node: &MemoryNode, event_type: &str, capture: bool) {
+        let listeners = self
+            .event_listeners
+            .borrow()
+            .iter()
+            .filter(|listener| {
+                listener.target == *node
+                    && listener.event_type == event_type
+                    && listener.options.capture == capture
+            })
+            .map(|listener| (listener.id, listener.options, Rc::clone(&listener.listener)))
+            .collect::<Vec<_>>();
+
+        for (id, options, listener) in listeners {
+            let is_registered = self
+                .event_listeners
+                .borrow()
+                .iter()
+                .any(|listener| listener.id == id);
+            if !is_registered {
+                continue;
+            }
+            if options.once {
+                self.event_listeners
+                    .borrow_mut()
+                    .retain(|listener| listener.id != id);
+            }
+            listener(Self::placeholder_event());
+        }
+    }
+
+    /// Events are not inspected, because the innermost dispatch has their data.
+    fn placeholder_event() -> web_sys::Event {
+        JsValue::UNDEFINED.unchecked_into()
+    }
+
+    fn with_dispatch<T>(&self, f: impl FnOnce(&mut MemoryDispatch) -> T) -> Option<T> {
+        self.dispatches.borrow_mut().last_mut().map(f)
+    }
+
+    fn record(&self, mutation: Mutation) {
+        self.mutations.borrow_mut().push(mutation);
+    }
+}
+
+impl DomBackend for MemoryDomBackend {
+    type Node = MemoryNode;
+    type EventListenerHandle = usize;
+
+    fn create_element(&self, tag_name: &str) -> Self::Node {
+        self.record(Mutation::CreateElement {
+            tag_name: String::from(tag_name),
+            namespace: None,
+        });
+        MemoryNode::new(MemoryNodeKind::Element {
+            tag_name: String::from(tag_name),
+            namespace: None,
+            attributes: vec![],
+            value: None,
+            checked: None,
+            properties: vec![],
+        })
+    }
+
+    fn create_element_ns(&self, namespace: &str, tag_name: &str) -> Self::Node {
+        self.record(Mutation::CreateElement {
+            tag_name: String::from(tag_name),
+            namespace: Some(String::from(namespace)),
+        });
+        MemoryNode::new(MemoryNodeKind::Element {
+            tag_name: String::from(tag_name),
+            namespace: Some(String::from(namespace)),
+            attributes: vec![],
+            value: None,
+            checked: None,
+            properties: vec![],
+        })
+    }
+
+    fn create_text_node(&self, text: &str) -> Self::Node {
+        self.record(Mutation::CreateTextNode {
+            text: String::from(text),
+        });
+        MemoryNode::new(MemoryNodeKind::Text(String::from(text)))
+    }
+
+    fn import_r_node(&self, _r_node: web_sys::Node) -> Self::Node {
+        MemoryNode::new(MemoryNodeKind::RNode)
+    }
+
+    fn to_r_node(&self, _node: &Self::Node) -> Option<web_sys::Node> {
+        None
+    }
+
+    fn tag_name(&self, node: &Self::Node) -> Option<String> {
+        node.tag_name()
+    }
+
+    fn child_nodes(&self, parent: &Self::Node) -> VecDeque<Self::Node> {
+        parent.children().into()
+    }
+
+    fn insert_before(
+        &self,
+        parent: &Self::Node,
+        node: &Self::Node,
+        reference: Option<&Self::Node>,
+    ) {
+        self.record(Mutation::InsertBefore);
+        node.detach();
+        let idx = reference.and_then(|reference| parent.index_of(reference));
+        let mut parent_data = parent.data.borrow_mut();
+        let idx = idx.unwrap_or(parent_data.children.len());
+        parent_data.children.insert(idx, node.clone());
+        node.data.borrow_mut().parent = Rc::downgrade(&parent.data);
+    }
+
+    fn replace_child(&self, parent: &Self::Node, node: &Self::Node, child: &Self::Node) {
+        self.record(Mutation::ReplaceChild);
+        node.detach();
+        if let Some(idx) = parent.index_of(child) {
+            parent.data.borrow_mut().children[idx] = node.clone();
+            node.data.borrow_mut().parent = Rc::downgrade(&parent.data);
+            child.data.borrow_mut().parent = Weak::new();
+        }
+    }
+
+    fn remove_child(&self, parent: &Self::Node, child: &Self::Node) {
+        self.record(Mutation::RemoveChild);
+        if let Some(idx) = parent.index_of(child) {
+            parent.data.borrow_mut().children.remove(idx);
+            child.data.borrow_mut().parent = Weak::new();
+        }
+    }
+
+    fn set_text(&self, node: &Self::Node, text: &str) {
+        self.record(Mutation::SetText {
+            text: String::from(text),
+        });
+        if let MemoryNodeKind::Text(now) = &mut node.data.borrow_mut().kind {
+            *now = String::from(text);
+        }
+    }
+
+    fn set_attribute(&self, node: &Self::Node, name: &str, value: &str) {
+        self.record(Mutation::SetAttribute {
+            name: String::from(name),
+            value: String::from(value),
+        });
+        if let MemoryNodeKind::Element { attributes, .. } = &mut node.data.borrow_mut().kind {
+            if let Some(attribute) = attributes.iter_mut().find(|(n, _)| n == name) {
+                attribute.1 = String::from(value);
+            } else {
+                attributes.push((String::from(name), String::from(value)));
+            }
+        }
+    }
+
+    fn remove_attribute(&self, node: &Self::Node, name: &str) {
+        self.record(Mutation::RemoveAttribute {
+            name: String::from(name),
+        });
+        if let MemoryNodeKind::Element { attributes, .. } = &mut node.data.borrow_mut().kind {
+            attributes.retain(|(n, _)| n != name);
+        }
+    }
+
+    fn set_attribute_ns(&self, node: &Self::Node, namespace: &str, name: &str, value: &str) {
+        self.record(Mutation::SetAttributeNs {
+            namespace: String::from(namespace),
+            name: String::from(name),
+            value: String::from(value),
+        });
+        node.set_attribute(name, value);
+    }
+
+    fn remove_attribute_ns(&self, node: &Self::Node, namespace: &str, name: &str) {
+        self.record(Mutation::RemoveAttributeNs {
+            namespace: String::from(namespace),
+            name: String::from(name),
+        });
+        if let MemoryNodeKind::Element { attributes, .. } = &mut node.data.borrow_mut().kind {
+            attributes.retain(|(n, _)| n != name);
+        }
+    }
+
+    fn add_class(&self, node: &Self::Node, name: &str) {
+        self.record(Mutation::AddClass {
+            name: String::from(name),
+        });
+        let mut classes = node.classes();
+        if !classes.iter().any(|class| class == name) {
+            classes.push(String::from(name));
+        }
+        node.set_attribute("class", classes.join(" "));
+    }
+
+    fn remove_class(&self, node: &Self::Node, name: &str) {
+        self.record(Mutation::RemoveClass {
+            name: String::from(name),
+        });
+        let mut classes = node.classes();
+        classes.retain(|class| class != name);
+        node.set_attribute("class", classes.join(" "));
+    }
+
+    fn set_style_property(&self, node: &Self::Node, name: &str, value: &str) {
+        self.record(Mutation::SetStyleProperty {
+            name: String::from(name),
+            value: String::from(value),
+        });
+        let mut styles = node.styles();
+        if let Some(style) = styles.iter_mut().find(|(n, _)| n == name) {
+            style.1 = String::from(value);
+        } else {
+            styles.push((String::from(name), String::from(value)));
+        }
+        node.set_styles(&styles);
+    }
+
+    fn remove_style_property(&self, node: &Self::Node, name: &str) {
+        self.record(Mutation::RemoveStyleProperty {
+            name: String::from(name),
+        });
+        let mut styles = node.styles();
+        styles.retain(|(n, _)| n != name);
+        node.set_styles(&styles);
+    }
+
+    fn get_value(&self, node: &Self::Node) -> Option<String> {
+        if node.is_form_control() {
+            node.value()
+        } else {
+            node.attribute("value")
+        }
+    }
+
+    fn set_value(&self, node: &Self::Node, value: &str) {
+        if node.is_form_control() {
+            self.record(Mutation::SetValue {
+                value: String::from(value),
+            });
+            if let MemoryNodeKind::Element { value: now, .. } = &mut node.data.borrow_mut().kind {
+                *now = Some(String::from(value));
+            }
+        } else {
+            self.set_attribute(node, "value", value);
+        }
+    }
+
+    fn get_checked(&self, node: &Self::Node) -> Option<bool> {
+        (node.tag_name()? == "input").then(|| node.checked())
+    }
+
+    /// Values of `option`s, or their text if they have no `value`.
+    fn selected_values(&self, node: &Self::Node) -> Option<Vec<String>> {
+        if node.tag_name()? != "select" {
+            return None;
+        }
+        let options = node.query_selector_all("option");
+        let options = options.into_iter().filter(|option| option.checked());
+        Some(
+            options
+                .map(|option| {
+                    option
+                        .attribute("value")
+                        .unwrap_or_else(|| option.text_content())
+                })
+                .collect(),
+        )
+    }
+
+    /// Files can not be made without browser, so `input` has no file.
+    fn files(&self, node: &Self::Node) -> Option<Vec<web_sys::File>> {
+        (node.tag_name()? == "input").then(Vec::new)
+    }
+
+    fn content_editable_text(&self, node: &Self::Node) -> Option<String> {
+        let content_editable = node.attribute("contenteditable")?;
+        (content_editable != "false").then(|| node.text_content())
+    }
+
+    /// Properties which are not set are `undefined`.
+    fn get_property(&self, node: &Self::Node, name: &str) -> Rc<JsValue> {
+        match &node.data.borrow().kind {
+            MemoryNodeKind::Element { properties, .. } => properties
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, value)| Rc::clone(value)),
+            _ => None,
+        }
+        .unwrap_or_else(|| Rc::new(JsValue::UNDEFINED))
+    }
+
+    /// Values are compared by identity, because they can not be inspected natively.
+    /// Constants like `JsValue::TRUE` are always identical.
+    fn is_property(&self, node: &Self::Node, name: &str, value: &JsValue) -> bool {
+        match &node.data.borrow().kind {
+            MemoryNodeKind::Element { properties, .. } => properties
+                .iter()
+                .any(|(n, live)| n == name && live.as_ref().into_abi() == value.into_abi()),
+            _ => false,
+        }
+    }
+
+    fn set_property(&self, node: &Self::Node, name: &str, value: Rc<JsValue>) {
+        self.record(Mutation::SetProperty {
+            name: String::from(name),
+        });
+        if let MemoryNodeKind::Element { properties, .. } = &mut node.data.borrow_mut().kind {
+            properties.retain(|(n, _)| n != name);
+            properties.push((String::from(name), value));
+        }
+    }
+
+    fn focus(&self, _node: &Self::Node) {
+        self.record(Mutation::Focus);
+    }
+
+    fn add_event_listener(
+        &self,
+        target: &Self::Node,
+        event_type: &str,
+        listener: DomEventListener,
+        options: DomEventListenerOptions,
+    ) -> Self::EventListenerHandle {
+        self.record(Mutation::AddEventListener {
+            event_type: String::from(event_type),
+            options,
+        });
+        let id = self.listener_count.get();
+        self.listener_count.set(id + 1);
+        self.event_listeners.borrow_mut().push(MemoryEventListener {
+            id,
+            target: target.clone(),
+            event_type: String::from(event_type),
+            options,
+            listener: Rc::new(listener),
+        });
+        id
+    }
+
+    fn remove_event_listener(
+        &self,
+        _target: &Self::Node,
+        event_type: &str,
+        listener: Self::EventListenerHandle,
+        capture: bool,
+    ) {
+        self.record(Mutation::RemoveEventListener {
+            event_type: String::from(event_type),
+            capture,
+        });
+        self.event_listeners
+            .borrow_mut()
+            .retain(|event_listener| event_listener.id != listener);
+    }
+
+    /// The target of the innermost event of `dispatch_event`.
+    /// Events which are not dispatched by `dispatch_event` have no target.
+    fn event_target(&self, _e: &web_sys::Event) -> Option<Self::Node> {
+        self.with_dispatch(|dispatch| dispatch.target.clone())
+    }
+
+    fn clone_event(&self, _e: &web_sys::Event) -> web_sys::Event {
+        Self::placeholder_event()
+    }
+
+    /// Events can not be inspected natively, so they are instances of every interface.
+    fn is_event_instance(
+        &self,
+        _e: &web_sys::Event,
+        _is_instance: &dyn Fn(&web_sys::Event) -> bool,
+    ) -> bool {
+        true
+    }
+
+    fn prevent_default(&self, _e: &web_sys::Event) {
+        self.with_dispatch(|dispatch| dispatch.default_prevented = true);
+    }
+
+    fn default_prevented(&self, _e: &web_sys::Event) -> bool {
+        self.with_dispatch(|dispatch| dispatch.default_prevented)
+            .unwrap_or(false)
+    }
+
+    fn stop_propagation(&self, _e: &web_sys::Event) {
+        self.with_dispatch(|dispatch| dispatch.propagation_stopped = true);
+    }
+
+    fn event_key(&self, _e: &web_sys::Event) -> Option<String> {
+        self.with_dispatch(|dispatch| dispatch.key.clone())
+            .flatten()
+    }
+
+    fn contains(&self, node: &Self::Node, other: &Self::Node) -> bool {
+        let mut other = Some(other.clone());
+        while let Some(now) = other {
+            if now == *node {
+                return true;
+            }
+            other = now.parent();
+        }
+        false
+    }
+
+    fn is_same_node(&self, node: &Self::Node, other: &Self::Node) -> bool {
+        node == other
+    }
+}
+
+impl std::default::Default for MemoryDomBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryNode {
+    fn new(kind: MemoryNodeKind) -> Self {
+        Self {
+            data: Rc::new(RefCell::new(MemoryNodeData {
+                kind,
+                parent: Weak::new(),
+                children: vec![],
+            })),
+        }
+    }
+
+    pub fn tag_name(&self) -> Option<String> {
+        match &self.data.borrow().kind {
+            MemoryNodeKind::Element { tag_name, .. } => Some(tag_name.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn namespace(&self) -> Option<String> {
+        match &self.data.borrow().kind {
+            MemoryNodeKind::Element { namespace, .. } => namespace.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        match &self.data.borrow().kind {
+            MemoryNodeKind::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, value)| value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Tokens of `class`.
+    pub fn classes(&self) -> Vec<String> {
+        self.attribute("class")
+            .map(|class| class.split_whitespace().map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    /// Declarations of `style`.
+    pub fn styles(&self) -> Vec<(String, String)> {
+        self.attribute("style")
+            .map(|style| {
+                style
+                    .split(';')
+                    .filter_map(|declaration| declaration.split_once(':'))
+                    .map(|(name, value)| (String::from(name.trim()), String::from(value.trim())))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Sets an attribute like others do. It is not recorded as `Mutation`.
+    pub fn set_attribute(&self, name: &str, value: impl Into<String>) {
+        if let MemoryNodeKind::Element { attributes, .. } = &mut self.data.borrow_mut().kind {
+            let value = value.into();
+            if let Some(attribute) = attributes.iter_mut().find(|(n, _)| n == name) {
+                attribute.1 = value;
+            } else {
+                attributes.push((String::from(name), value));
+            }
+        }
+    }
+
+    fn set_styles(&self, styles: &[(String, String)]) {
+        let style = styles
+            .iter()
+            .map(|(name, value)| format!("{}:{}", name, value))
+            .collect::<Vec<_>>()
+            .join(";");
+        self.set_attribute("style", style);
+    }
+
+    fn is_form_control(&self) -> bool {
+        self.tag_name()
+            .map(|tag_name| tag_name == "input" || tag_name == "textarea" || tag_name == "select")
+            .unwrap_or(false)
+    }
+
+    /// `value` of `input`, `textarea` and `select`.
+    pub fn value(&self) -> Option<String> {
+        match &self.data.borrow().kind {
+            MemoryNodeKind::Element { value, .. } => value.clone(),
+            _ => None,
+        }
+    }
+
+    /// Sets `value` of `input`, `textarea` and `select` like a user does. It is not recorded as `Mutation`.
+    pub fn set_value(&self, value: &str) {
+        if let MemoryNodeKind::Element { value: now, .. } = &mut self.data.borrow_mut().kind {
+            *now = Some(String::from(value));
+        }
+    }
+
+    /// `checked` of `input`, or `selected` of `option`.
+    pub fn checked(&self) -> bool {
+        let checked = match &self.data.borrow().kind {
+            MemoryNodeKind::Element { checked, .. } => *checked,
+            _ => return false,
+        };
+        checked.unwrap_or_else(|| {
+            let attribute = match self.tag_name().as_deref() {
+                Some("option") => "selected",
+                _ => "checked",
+            };
+            self.attribute(attribute).is_some()
+        })
+    }
+
+    /// Sets `checked` of `input`, or `selected` of `option`, like a user does.
+    /// It is not recorded as `Mutation`.
+    pub fn set_checked(&self, checked: bool) {
+        if let MemoryNodeKind::Element { checked: now, .. } = &mut self.data.borrow_mut().kind {
+            *now = Some(checked);
+        }
+    }
+
+    pub fn has_property(&self, name: &str) -> bool {
+        match &self.data.borrow().kind {
+            MemoryNodeKind::Element { properties, .. } => properties.iter().any(|(n, _)| n == name),
+            _ => false,
+        }
+    }
+
+    pub fn text(&self) -> Option<String> {
+        match &self.data.borrow().kind {
+            MemoryNodeKind::Text(text) => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn text_content(&self) -> String {
+        match &self.data.borrow().kind {
+            MemoryNodeKind::Text(text) => text.clone(),
+            _ => self
+                .children()
+                .iter()
+                .map(|child| child.text_content())
+                .collect(),
+        }
+    }
+
+    pub fn children(&self) -> Vec<MemoryNode> {
+        self.data.borrow().children.clone()
+    }
+
+    /// The first descendant element which matches `selector`, like `querySelector`.
+    /// Selectors which are not supported by `Selector` match nothing.
+    pub fn query_selector(&self, selector: &str) -> Option<MemoryNode> {
+        self.query_selector_all(selector).into_iter().next()
+    }
+
+    /// Descendant elements which match `selector` in document order, like `querySelectorAll`.
+    pub fn query_selector_all(&self, selector: &str) -> Vec<MemoryNode> {
+        let mut nodes = vec![];
+        if let Some(selector) = Selector::parse(selector) {
+            self.collect_descendants(&selector, &mut nodes);
+        }
+        nodes
+    }
+
+    fn collect_descendants(&self, selector: &Selector, nodes: &mut Vec<MemoryNode>) {
+        for child in self.children() {
+            if selector.matches(&child) {
+                nodes.push(child.clone());
+            }
+            child.collect_descendants(selector, nodes);
+        }
+    }
+
+    pub fn parent(&self) -> Option<MemoryNode> {
+        self.data
+            .borrow()
+            .parent
+            .upgrade()
+            .map(|data| MemoryNode { data })
+    }
+
+    pub fn inner_html(&self) -> String {
+        let is_raw_text = self
+            .tag_name()
+            .map(|tag_name| tag_name == "script" || tag_name == "style")
+            .unwrap_or(false);
+        self.children()
+            .iter()
+            .map(|child| child.outer_html_in(is_raw_text))
+            .collect()
+    }
+
+    pub fn outer_html(&self) -> String {
+        self.outer_html_in(false)
+    }
+
+    fn outer_html_in(&self, is_raw_text: bool) -> String {
+        match &self.data.borrow().kind {
+            MemoryNodeKind::Text(text) if is_raw_text => text.clone(),
+            MemoryNodeKind::Text(text) => escape_text(text),
+            MemoryNodeKind::RNode => String::from("<!--RNode-->"),
+            MemoryNodeKind::Element {
+                tag_name,
+                namespace,
+                attributes,
+                ..
+            } => {
+                let mut html = format!("<{}", tag_name);
+                for (name, value) in attributes {
+                    html += &format!(" {}=\"{}\"", name, escape_attribute(value));
+                }
+                html.push('>');
+                if namespace.is_none() && VOID_ELEMENTS.contains(&tag_name.as_str()) {
+                    return html;
+                }
+                html += &self.inner_html();
+                html += &format!("</{}>", tag_name);
+                html
+            }
+        }
+    }
+
+    fn index_of(&self, child: &MemoryNode) -> Option<usize> {
+        self.data
+            .borrow()
+            .children
+            .iter()
+            .position(|now| now == child)
+    }
+
+    fn detach(&self) {
+        if let Some(parent) = self.parent() {
+            if let Some(idx) = parent.index_of(self) {
+                parent.data.borrow_mut().children.remove(idx);
+            }
+        }
+        self.data.borrow_mut().parent = Weak::new();
+    }
+}
+
+impl std::cmp::PartialEq for MemoryNode {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl std::fmt::Debug for MemoryNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.outer_html())
+    }
+}
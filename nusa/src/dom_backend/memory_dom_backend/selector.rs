@@ -0,0 +1,213 @@
+//! A subset of CSS selectors to query `MemoryNode`s.
+//! It supports type and universal selectors, `#id`, `.class`, `[name]`, `[name="value"]`,
+//! descendant and child combinators, and selector lists.
+
+use super::MemoryNode;
+
+pub struct Selector {
+    complexes: Vec<Vec<(Combinator, Compound)>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Default)]
+struct Compound {
+    tag_name: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Selector {
+    /// Returns `None` if `selector` is invalid or not supported.
+    pub fn parse(selector: &str) -> Option<Self> {
+        let complexes = selector
+            .split(',')
+            .map(Self::parse_complex)
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { complexes })
+    }
+
+    pub fn matches(&self, node: &MemoryNode) -> bool {
+        self.complexes
+            .iter()
+            .any(|complex| Self::matches_complex(complex, node))
+    }
+
+    fn parse_complex(selector: &str) -> Option<Vec<(Combinator, Compound)>> {
+        let mut tokens = vec![];
+        let mut token = String::new();
+        let mut in_bracket = false;
+        for c in selector.chars() {
+            match c {
+                '[' => {
+                    in_bracket = true;
+                    token.push(c);
+                }
+                ']' => {
+                    in_bracket = false;
+                    token.push(c);
+                }
+                _ if in_bracket => token.push(c),
+                '>' => {
+                    tokens.extend((!token.is_empty()).then(|| std::mem::take(&mut token)));
+                    tokens.push(String::from(">"));
+                }
+                _ if c.is_whitespace() => {
+                    tokens.extend((!token.is_empty()).then(|| std::mem::take(&mut token)));
+                }
+                _ => token.push(c),
+            }
+        }
+        tokens.extend((!token.is_empty()).then_some(token));
+
+        let mut complex = vec![];
+        let mut combinator = Combinator::Descendant;
+        for token in tokens {
+            if token == ">" {
+                if complex.is_empty() || combinator == Combinator::Child {
+                    return None;
+                }
+                combinator = Combinator::Child;
+            } else {
+                complex.push((combinator, Compound::parse(&token)?));
+                combinator = Combinator::Descendant;
+            }
+        }
+
+        if complex.is_empty() || combinator == Combinator::Child {
+            None
+        } else {
+            Some(complex)
+        }
+    }
+
+    /// Matches compounds from the last one, which is `node`, to ancestors.
+    fn matches_complex(complex: &[(Combinator, Compound)], node: &MemoryNode) -> bool {
+        let ((combinator, compound), rest) = match complex.split_last() {
+            Some(last) => last,
+            None => return true,
+        };
+        if !compound.matches(node) {
+            return false;
+        }
+        if rest.is_empty() {
+            return true;
+        }
+
+        match combinator {
+            Combinator::Child => node
+                .parent()
+                .is_some_and(|parent| Self::matches_complex(rest, &parent)),
+            Combinator::Descendant => {
+                let mut ancestor = node.parent();
+                while let Some(now) = ancestor {
+                    if Self::matches_complex(rest, &now) {
+                        return true;
+                    }
+                    ancestor = now.parent();
+                }
+                false
+            }
+        }
+    }
+}
+
+impl Compound {
+    fn parse(selector: &str) -> Option<Self> {
+        let mut compound = Self::default();
+        let mut rest = selector;
+
+        if let Some(now) = rest.strip_prefix('*') {
+            rest = now;
+        } else {
+            let (tag_name, now) = Self::split_ident(rest);
+            if !tag_name.is_empty() {
+                compound.tag_name = Some(tag_name.to_lowercase());
+            }
+            rest = now;
+        }
+
+        while let Some(c) = rest.chars().next() {
+            rest = &rest[c.len_utf8()..];
+            match c {
+                '#' => {
+                    let (id, now) = Self::split_ident(rest);
+                    compound.id = Some(String::from(Self::non_empty(id)?));
+                    rest = now;
+                }
+                '.' => {
+                    let (class, now) = Self::split_ident(rest);
+                    compound.classes.push(String::from(Self::non_empty(class)?));
+                    rest = now;
+                }
+                '[' => {
+                    let (attribute, now) = rest.split_once(']')?;
+                    let attribute = match attribute.split_once('=') {
+                        Some((name, value)) => {
+                            let value = value.trim();
+                            let value = value
+                                .strip_prefix('"')
+                                .and_then(|value| value.strip_suffix('"'))
+                                .or_else(|| {
+                                    value
+                                        .strip_prefix('\'')
+                                        .and_then(|value| value.strip_suffix('\''))
+                                })
+                                .unwrap_or(value);
+                            (name.trim(), Some(String::from(value)))
+                        }
+                        None => (attribute.trim(), None),
+                    };
+                    let name = String::from(Self::non_empty(attribute.0)?);
+                    compound.attributes.push((name, attribute.1));
+                    rest = now;
+                }
+                _ => return None,
+            }
+        }
+
+        Some(compound)
+    }
+
+    fn split_ident(selector: &str) -> (&str, &str) {
+        let len = selector
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(selector.len());
+        selector.split_at(len)
+    }
+
+    fn non_empty(ident: &str) -> Option<&str> {
+        (!ident.is_empty()).then_some(ident)
+    }
+
+    fn matches(&self, node: &MemoryNode) -> bool {
+        let tag_name = match node.tag_name() {
+            Some(tag_name) => tag_name,
+            None => return false,
+        };
+
+        self.tag_name
+            .as_ref()
+            .is_none_or(|name| name.eq_ignore_ascii_case(&tag_name))
+            && self
+                .id
+                .as_ref()
+                .is_none_or(|id| node.attribute("id").as_ref() == Some(id))
+            && self
+                .classes
+                .iter()
+                .all(|class| node.classes().contains(class))
+            && self.attributes.iter().all(|(name, value)| {
+                let now = node.attribute(name);
+                match value {
+                    Some(value) => now.as_ref() == Some(value),
+                    None => now.is_some(),
+                }
+            })
+    }
+}
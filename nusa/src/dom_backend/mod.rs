@@ -0,0 +1,130 @@
+use std::collections::VecDeque;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+pub mod memory_dom_backend;
+pub mod web_sys_dom_backend;
+
+pub use memory_dom_backend::MemoryDomBackend;
+pub use web_sys_dom_backend::WebSysDomBackend;
+
+/// It is `Fn`, because events can be dispatched in a listener re-entrantly.
+pub type DomEventListener = Box<dyn Fn(web_sys::Event)>;
+
+/// Options of `addEventListener`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct DomEventListenerOptions {
+    pub capture: bool,
+    pub passive: bool,
+    pub once: bool,
+}
+
+/// Operations which `DomRenderer` and `DomEvents` apply to a real-DOM.
+pub trait DomBackend: Clone + 'static {
+    type Node: Clone + 'static;
+
+    /// A registered listener, which is given to `remove_event_listener`.
+    type EventListenerHandle: 'static;
+
+    fn create_element(&self, tag_name: &str) -> Self::Node;
+    fn create_element_ns(&self, namespace: &str, tag_name: &str) -> Self::Node;
+    fn create_text_node(&self, text: &str) -> Self::Node;
+
+    /// Converts a node given by `Html::node`.
+    fn import_r_node(&self, r_node: web_sys::Node) -> Self::Node;
+
+    /// Converts a node to give it to `Events::refer`.
+    /// Returns `None` if the backend has no `web_sys::Node`.
+    fn to_r_node(&self, node: &Self::Node) -> Option<web_sys::Node>;
+
+    /// The local name of an element, like `input`.
+    fn tag_name(&self, node: &Self::Node) -> Option<String>;
+
+    /// Element and text children of `parent`.
+    fn child_nodes(&self, parent: &Self::Node) -> VecDeque<Self::Node>;
+
+    fn insert_before(&self, parent: &Self::Node, node: &Self::Node, reference: Option<&Self::Node>);
+    fn replace_child(&self, parent: &Self::Node, node: &Self::Node, child: &Self::Node);
+    fn remove_child(&self, parent: &Self::Node, child: &Self::Node);
+
+    fn set_text(&self, node: &Self::Node, text: &str);
+    fn set_attribute(&self, node: &Self::Node, name: &str, value: &str);
+    fn remove_attribute(&self, node: &Self::Node, name: &str);
+
+    /// `setAttributeNS` and `removeAttributeNS`. `name` is a qualified name like `xlink:href`.
+    fn set_attribute_ns(&self, node: &Self::Node, namespace: &str, name: &str, value: &str);
+    fn remove_attribute_ns(&self, node: &Self::Node, namespace: &str, name: &str);
+
+    /// `classList.add` and `classList.remove`, which keep classes set by others.
+    fn add_class(&self, node: &Self::Node, name: &str);
+    fn remove_class(&self, node: &Self::Node, name: &str);
+
+    /// `style.setProperty` and `style.removeProperty`, which keep styles set by others.
+    fn set_style_property(&self, node: &Self::Node, name: &str, value: &str);
+    fn remove_style_property(&self, node: &Self::Node, name: &str);
+
+    /// The live `value` of form controls, or `value` attribute of other elements.
+    fn get_value(&self, node: &Self::Node) -> Option<String>;
+    /// Sets `value` of form controls, or `value` attribute of other elements.
+    /// It keeps the selection of focused controls.
+    fn set_value(&self, node: &Self::Node, value: &str);
+
+    /// `checked` of `input`.
+    fn get_checked(&self, node: &Self::Node) -> Option<bool>;
+    /// Values of selected options of `select`.
+    fn selected_values(&self, node: &Self::Node) -> Option<Vec<String>>;
+    /// Files of `input`.
+    fn files(&self, node: &Self::Node) -> Option<Vec<web_sys::File>>;
+    /// `innerText` of an element whose `contenteditable` is enabled.
+    fn content_editable_text(&self, node: &Self::Node) -> Option<String>;
+
+    /// The live property `name`, which is kept to restore it.
+    fn get_property(&self, node: &Self::Node, name: &str) -> Rc<JsValue>;
+    /// Whether the live property `name` is `value` by `===`.
+    fn is_property(&self, node: &Self::Node, name: &str, value: &JsValue) -> bool;
+    fn set_property(&self, node: &Self::Node, name: &str, value: Rc<JsValue>);
+
+    /// Moves focus to `node`. It is called for `autofocus` after the node is inserted.
+    fn focus(&self, node: &Self::Node);
+
+    fn add_event_listener(
+        &self,
+        target: &Self::Node,
+        event_type: &str,
+        listener: DomEventListener,
+        options: DomEventListenerOptions,
+    ) -> Self::EventListenerHandle;
+
+    fn remove_event_listener(
+        &self,
+        target: &Self::Node,
+        event_type: &str,
+        listener: Self::EventListenerHandle,
+        capture: bool,
+    );
+
+    /// The node which an event is dispatched to.
+    fn event_target(&self, e: &web_sys::Event) -> Option<Self::Node>;
+
+    /// Copies an event to give it to each handler.
+    fn clone_event(&self, e: &web_sys::Event) -> web_sys::Event;
+
+    /// Whether `e` is an instance of an event interface, which `is_instance` checks.
+    fn is_event_instance(
+        &self,
+        e: &web_sys::Event,
+        is_instance: &dyn Fn(&web_sys::Event) -> bool,
+    ) -> bool;
+
+    /// `preventDefault` of an event which is dispatched now.
+    fn prevent_default(&self, e: &web_sys::Event);
+    fn default_prevented(&self, e: &web_sys::Event) -> bool;
+    fn stop_propagation(&self, e: &web_sys::Event);
+
+    /// `key` of a keyboard event.
+    fn event_key(&self, e: &web_sys::Event) -> Option<String>;
+
+    /// Returns whether `other` is an inclusive descendant of `node`.
+    fn contains(&self, node: &Self::Node, other: &Self::Node) -> bool;
+    fn is_same_node(&self, node: &Self::Node, other: &Self::Node) -> bool;
+}
@@ -0,0 +1,336 @@
+use super::{DomBackend, DomEventListener, DomEventListenerOptions};
+use std::collections::VecDeque;
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[derive(Clone)]
+pub struct WebSysDomBackend {
+    document: web_sys::Document,
+}
+
+impl WebSysDomBackend {
+    pub fn new() -> Self {
+        Self::with_document(web_sys::window().unwrap().document().unwrap())
+    }
+
+    pub fn with_document(document: web_sys::Document) -> Self {
+        Self { document }
+    }
+
+    pub fn document(&self) -> &web_sys::Document {
+        &self.document
+    }
+
+    /// `style` of HTML, SVG and MathML elements.
+    fn style(node: &web_sys::Node) -> Option<web_sys::CssStyleDeclaration> {
+        js_sys::Reflect::get(node, &JsValue::from_str("style"))
+            .ok()
+            .and_then(|style| style.dyn_into().ok())
+    }
+}
+
+impl DomBackend for WebSysDomBackend {
+    type Node = web_sys::Node;
+    type EventListenerHandle = Closure<dyn Fn(web_sys::Event)>;
+
+    fn create_element(&self, tag_name: &str) -> Self::Node {
+        self.document.create_element(tag_name).unwrap().into()
+    }
+
+    fn create_element_ns(&self, namespace: &str, tag_name: &str) -> Self::Node {
+        self.document
+            .create_element_ns(Some(namespace), tag_name)
+            .unwrap()
+            .into()
+    }
+
+    fn create_text_node(&self, text: &str) -> Self::Node {
+        self.document.create_text_node(text).into()
+    }
+
+    fn import_r_node(&self, r_node: web_sys::Node) -> Self::Node {
+        r_node
+    }
+
+    fn to_r_node(&self, node: &Self::Node) -> Option<web_sys::Node> {
+        Some(node.clone())
+    }
+
+    fn tag_name(&self, node: &Self::Node) -> Option<String> {
+        node.dyn_ref::<web_sys::Element>()
+            .map(|element| element.local_name())
+    }
+
+    fn child_nodes(&self, parent: &Self::Node) -> VecDeque<Self::Node> {
+        let raws = parent.child_nodes();
+        let mut buf = VecDeque::new();
+        let raws_len = raws.length();
+        for i in 0..raws_len {
+            if let Some(raw) = raws.get(i) {
+                let node_type = raw.node_type();
+                if node_type == 1 || node_type == 3 {
+                    buf.push_back(raw);
+                }
+            }
+        }
+        buf
+    }
+
+    fn insert_before(
+        &self,
+        parent: &Self::Node,
+        node: &Self::Node,
+        reference: Option<&Self::Node>,
+    ) {
+        if let Err(err) = parent.insert_before(node, reference) {
+            web_sys::console::log_1(&err);
+            panic!();
+        }
+    }
+
+    fn replace_child(&self, parent: &Self::Node, node: &Self::Node, child: &Self::Node) {
+        let _ = parent.replace_child(node, child);
+    }
+
+    fn remove_child(&self, parent: &Self::Node, child: &Self::Node) {
+        let _ = parent.remove_child(child);
+    }
+
+    fn set_text(&self, node: &Self::Node, text: &str) {
+        if let Some(node) = node.dyn_ref::<web_sys::CharacterData>() {
+            node.set_data(text);
+        }
+    }
+
+    fn set_attribute(&self, node: &Self::Node, name: &str, value: &str) {
+        if let Some(node) = node.dyn_ref::<web_sys::Element>() {
+            let _ = node.set_attribute(name, value);
+        }
+    }
+
+    fn remove_attribute(&self, node: &Self::Node, name: &str) {
+        if let Some(node) = node.dyn_ref::<web_sys::Element>() {
+            let _ = node.remove_attribute(name);
+        }
+    }
+
+    fn set_attribute_ns(&self, node: &Self::Node, namespace: &str, name: &str, value: &str) {
+        if let Some(node) = node.dyn_ref::<web_sys::Element>() {
+            let _ = node.set_attribute_ns(Some(namespace), name, value);
+        }
+    }
+
+    fn remove_attribute_ns(&self, node: &Self::Node, namespace: &str, name: &str) {
+        if let Some(node) = node.dyn_ref::<web_sys::Element>() {
+            let local_name = name.split_once(':').map(|(_, local_name)| local_name);
+            let _ = node.remove_attribute_ns(Some(namespace), local_name.unwrap_or(name));
+        }
+    }
+
+    fn add_class(&self, node: &Self::Node, name: &str) {
+        if let Some(node) = node.dyn_ref::<web_sys::Element>() {
+            let _ = node.class_list().add_1(name);
+        }
+    }
+
+    fn remove_class(&self, node: &Self::Node, name: &str) {
+        if let Some(node) = node.dyn_ref::<web_sys::Element>() {
+            let _ = node.class_list().remove_1(name);
+        }
+    }
+
+    fn set_style_property(&self, node: &Self::Node, name: &str, value: &str) {
+        if let Some(style) = Self::style(node) {
+            let _ = style.set_property(name, value);
+        }
+    }
+
+    fn remove_style_property(&self, node: &Self::Node, name: &str) {
+        if let Some(style) = Self::style(node) {
+            let _ = style.remove_property(name);
+        }
+    }
+
+    fn get_value(&self, node: &Self::Node) -> Option<String> {
+        if let Some(node) = node.dyn_ref::<web_sys::HtmlInputElement>() {
+            Some(node.value())
+        } else if let Some(node) = node.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            Some(node.value())
+        } else if let Some(node) = node.dyn_ref::<web_sys::HtmlSelectElement>() {
+            Some(node.value())
+        } else if let Some(element) = node.dyn_ref::<web_sys::Element>() {
+            element.get_attribute("value")
+        } else {
+            None
+        }
+    }
+
+    fn set_value(&self, node: &Self::Node, value: &str) {
+        let is_focused = self
+            .document
+            .active_element()
+            .map(|element| element.is_same_node(Some(node)))
+            .unwrap_or(false);
+        let len = value.encode_utf16().count() as u32;
+
+        if let Some(node) = node.dyn_ref::<web_sys::HtmlInputElement>() {
+            let selection = (node.selection_start(), node.selection_end());
+            node.set_value(value);
+            if let (true, Ok(Some(start)), Ok(Some(end))) = (is_focused, selection.0, selection.1) {
+                let _ = node.set_selection_range(start.min(len), end.min(len));
+            }
+        } else if let Some(node) = node.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            let selection = (node.selection_start(), node.selection_end());
+            node.set_value(value);
+            if let (true, Ok(Some(start)), Ok(Some(end))) = (is_focused, selection.0, selection.1) {
+                let _ = node.set_selection_range(start.min(len), end.min(len));
+            }
+        } else if let Some(node) = node.dyn_ref::<web_sys::HtmlSelectElement>() {
+            node.set_value(value);
+        } else {
+            self.set_attribute(node, "value", value);
+        }
+    }
+
+    fn get_checked(&self, node: &Self::Node) -> Option<bool> {
+        node.dyn_ref::<web_sys::HtmlInputElement>()
+            .map(|node| node.checked())
+    }
+
+    fn selected_values(&self, node: &Self::Node) -> Option<Vec<String>> {
+        let options = node
+            .dyn_ref::<web_sys::HtmlSelectElement>()?
+            .selected_options();
+        let mut values = vec![];
+        for i in 0..options.length() {
+            if let Some(option) = options
+                .item(i)
+                .and_then(|option| option.dyn_into::<web_sys::HtmlOptionElement>().ok())
+            {
+                values.push(option.value());
+            }
+        }
+        Some(values)
+    }
+
+    fn files(&self, node: &Self::Node) -> Option<Vec<web_sys::File>> {
+        let input = node.dyn_ref::<web_sys::HtmlInputElement>()?;
+        let mut files = vec![];
+        if let Some(file_list) = input.files() {
+            for i in 0..file_list.length() {
+                if let Some(file) = file_list.item(i) {
+                    files.push(file);
+                }
+            }
+        }
+        Some(files)
+    }
+
+    fn content_editable_text(&self, node: &Self::Node) -> Option<String> {
+        node.dyn_ref::<web_sys::HtmlElement>()
+            .filter(|element| element.is_content_editable())
+            .map(|element| element.inner_text())
+    }
+
+    fn get_property(&self, node: &Self::Node, name: &str) -> Rc<JsValue> {
+        let value = js_sys::Reflect::get(node, &JsValue::from_str(name));
+        Rc::new(value.unwrap_or(JsValue::UNDEFINED))
+    }
+
+    fn is_property(&self, node: &Self::Node, name: &str, value: &JsValue) -> bool {
+        js_sys::Reflect::get(node, &JsValue::from_str(name))
+            .map(|live| live == *value)
+            .unwrap_or(false)
+    }
+
+    fn set_property(&self, node: &Self::Node, name: &str, value: Rc<JsValue>) {
+        let _ = js_sys::Reflect::set(node, &JsValue::from_str(name), &value);
+    }
+
+    fn focus(&self, node: &Self::Node) {
+        if let Some(node) = node.dyn_ref::<web_sys::HtmlElement>() {
+            let _ = node.focus();
+        }
+    }
+
+    fn add_event_listener(
+        &self,
+        target: &Self::Node,
+        event_type: &str,
+        listener: DomEventListener,
+        options: DomEventListenerOptions,
+    ) -> Self::EventListenerHandle {
+        let listener = Closure::wrap(listener);
+        let init = web_sys::AddEventListenerOptions::new();
+        init.set_capture(options.capture);
+        init.set_passive(options.passive);
+        init.set_once(options.once);
+        let _ = target.add_event_listener_with_callback_and_add_event_listener_options(
+            event_type,
+            listener.as_ref().unchecked_ref(),
+            &init,
+        );
+        listener
+    }
+
+    fn remove_event_listener(
+        &self,
+        target: &Self::Node,
+        event_type: &str,
+        listener: Self::EventListenerHandle,
+        capture: bool,
+    ) {
+        let _ = target.remove_event_listener_with_callback_and_bool(
+            event_type,
+            listener.as_ref().unchecked_ref(),
+            capture,
+        );
+    }
+
+    fn event_target(&self, e: &web_sys::Event) -> Option<Self::Node> {
+        e.target()
+            .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+    }
+
+    fn clone_event(&self, e: &web_sys::Event) -> web_sys::Event {
+        e.clone()
+    }
+
+    fn is_event_instance(
+        &self,
+        e: &web_sys::Event,
+        is_instance: &dyn Fn(&web_sys::Event) -> bool,
+    ) -> bool {
+        is_instance(e)
+    }
+
+    fn prevent_default(&self, e: &web_sys::Event) {
+        e.prevent_default();
+    }
+
+    fn default_prevented(&self, e: &web_sys::Event) -> bool {
+        e.default_prevented()
+    }
+
+    fn stop_propagation(&self, e: &web_sys::Event) {
+        e.stop_propagation();
+    }
+
+    fn event_key(&self, e: &web_sys::Event) -> Option<String> {
+        e.dyn_ref::<web_sys::KeyboardEvent>().map(|e| e.key())
+    }
+
+    fn contains(&self, node: &Self::Node, other: &Self::Node) -> bool {
+        node.contains(Some(other))
+    }
+
+    fn is_same_node(&self, node: &Self::Node, other: &Self::Node) -> bool {
+        node.is_same_node(Some(other))
+    }
+}
+
+impl std::default::Default for WebSysDomBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,73 @@
+//! Commands which touch the DOM after it is committed.
+//!
+//! ```ignore
+//! // in update
+//! Msg::AddItem(item) => {
+//!     self.items.push(item);
+//!     dom_cmd::scroll_into_view(self.last_item.clone())
+//! }
+//! ```
+
+use crate::dom_node;
+use crate::dom_ref::DomRef;
+use kagura::component::Cmd;
+use kagura::Component;
+
+/// Runs `f` after `BasicDomNode` commits the DOM of the current update.
+pub fn after_commit<C: Component + 'static>(f: impl FnOnce() -> Cmd<C> + 'static) -> Cmd<C> {
+    let committed = dom_node::committed();
+    Cmd::task(async move {
+        committed.await;
+        f()
+    })
+}
+
+/// Moves focus to `target`.
+pub fn focus<C: Component + 'static>(target: impl DomRef + 'static) -> Cmd<C> {
+    after_commit(move || {
+        if let Some(element) = target.element::<web_sys::HtmlElement>() {
+            let _ = element.focus();
+        }
+        Cmd::none()
+    })
+}
+
+/// Removes focus from `target`.
+pub fn blur<C: Component + 'static>(target: impl DomRef + 'static) -> Cmd<C> {
+    after_commit(move || {
+        if let Some(element) = target.element::<web_sys::HtmlElement>() {
+            let _ = element.blur();
+        }
+        Cmd::none()
+    })
+}
+
+/// Scrolls ancestors of `target` so that it is visible.
+pub fn scroll_into_view<C: Component + 'static>(target: impl DomRef + 'static) -> Cmd<C> {
+    after_commit(move || {
+        if let Some(element) = target.element::<web_sys::Element>() {
+            element.scroll_into_view();
+        }
+        Cmd::none()
+    })
+}
+
+/// Scrolls the content of `target` to `(x, y)`.
+pub fn scroll_to<C: Component + 'static>(target: impl DomRef + 'static, x: f64, y: f64) -> Cmd<C> {
+    after_commit(move || {
+        if let Some(element) = target.element::<web_sys::Element>() {
+            element.scroll_to_with_x_and_y(x, y);
+        }
+        Cmd::none()
+    })
+}
+
+/// Scrolls the page to `(x, y)`.
+pub fn scroll_window_to<C: Component + 'static>(x: f64, y: f64) -> Cmd<C> {
+    after_commit(move || {
+        if let Some(window) = web_sys::window() {
+            window.scroll_to_with_x_and_y(x, y);
+        }
+        Cmd::none()
+    })
+}
@@ -1,70 +1,93 @@
-use async_std::sync::{Arc, Mutex};
-use std::collections::VecDeque;
-use std::future::Future;
-use std::pin::Pin;
-use std::task::{Context, Poll, Waker};
-use wasm_bindgen::{prelude::*, JsCast};
+use super::MsgQueue;
+use crate::dom_backend::{DomBackend, DomEventListenerOptions};
+use crate::dom_renderer::VEventListener;
+use std::cell::RefCell;
+use std::rc::Rc;
 
+/// A native listener of an event type. It is removed when dropped.
+///
+/// The listener calls handlers synchronously for every event, so that handlers can call
+/// `preventDefault`. An event which is dispatched by a handler, like `element.click()`, is handled
+/// before the handler returns. Only messages of handlers are queued until the next render.
 pub struct DomEvent {
-    state: Arc<Mutex<State>>,
-}
-
-pub struct DomEventPoller {
-    state: Arc<Mutex<State>>,
+    state: Rc<RefCell<State>>,
+    remove: Option<Box<dyn FnOnce()>>,
 }
 
 struct State {
-    event_queue: VecDeque<web_sys::Event>,
-    waker: Option<Waker>,
+    // shared with running dispatches, so that they keep it even if it is replaced.
+    listener: Option<Rc<VEventListener>>,
+    msg_queue: MsgQueue,
 }
 
 impl DomEvent {
-    pub fn new(target: &web_sys::EventTarget, event_type: &str) -> Self {
-        let state = Arc::new(Mutex::new(State {
-            event_queue: VecDeque::new(),
-            waker: None,
+    /// Listens `event_type` in capture phase.
+    pub fn new<B: DomBackend>(
+        backend: &B,
+        target: &B::Node,
+        event_type: &str,
+        msg_queue: MsgQueue,
+    ) -> Self {
+        let options = DomEventListenerOptions {
+            capture: true,
+            ..Default::default()
+        };
+        Self::with_options(backend, target, event_type, options, msg_queue)
+    }
+
+    pub fn with_options<B: DomBackend>(
+        backend: &B,
+        target: &B::Node,
+        event_type: &str,
+        options: DomEventListenerOptions,
+        msg_queue: MsgQueue,
+    ) -> Self {
+        let state = Rc::new(RefCell::new(State {
+            listener: None,
+            msg_queue,
         }));
 
-        let a = Closure::wrap(Box::new({
-            let state = Arc::clone(&state);
-            move |e| {
-                let state = Arc::clone(&state);
-                wasm_bindgen_futures::spawn_local(async move {
-                    let mut state = state.lock_arc().await;
-                    state.event_queue.push_back(e);
-                    if let Some(waker) = state.waker.take() {
-                        waker.wake();
-                    }
-                });
-            }
-        }) as Box<dyn FnMut(web_sys::Event)>);
-        let _ = target.add_event_listener_with_callback_and_bool(
-            event_type,
-            a.as_ref().unchecked_ref(),
-            true,
-        );
-        a.forget();
+        let listener = Box::new({
+            let state = Rc::clone(&state);
+            move |e| Self::dispatch(&state, e)
+        });
+        let listener = backend.add_event_listener(target, event_type, listener, options);
 
-        Self { state }
-    }
+        let remove = Box::new({
+            let backend = backend.clone();
+            let target = target.clone();
+            let event_type = String::from(event_type);
+            move || backend.remove_event_listener(&target, &event_type, listener, options.capture)
+        });
 
-    pub fn poll(&self) -> DomEventPoller {
-        DomEventPoller {
-            state: Arc::clone(&self.state),
+        Self {
+            state,
+            remove: Some(remove),
         }
     }
-}
 
-impl Future for DomEventPoller {
-    type Output = web_sys::Event;
+    /// Sets a listener of the current tree.
+    pub fn set_listener(&self, listener: Option<VEventListener>) {
+        self.state.borrow_mut().listener = listener.map(Rc::new);
+    }
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        if let Some(mut state) = self.as_mut().state.try_lock_arc() {
-            state.waker = Some(cx.waker().clone());
-            if let Some(event) = state.event_queue.pop_front() {
-                return Poll::Ready(event);
+    fn dispatch(state: &Rc<RefCell<State>>, e: web_sys::Event) {
+        // the state is not borrowed while the listener runs, because handlers may dispatch
+        // another event, which calls this re-entrantly.
+        let listener = state.borrow().listener.clone();
+        if let Some(listener) = listener {
+            let (_, msgs) = listener(e);
+            if !msgs.is_empty() {
+                state.borrow().msg_queue.push(msgs);
             }
         }
-        Poll::Pending
+    }
+}
+
+impl std::ops::Drop for DomEvent {
+    fn drop(&mut self) {
+        if let Some(remove) = self.remove.take() {
+            remove();
+        }
     }
 }
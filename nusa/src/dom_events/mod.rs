@@ -1,82 +1,132 @@
+use crate::dom_backend::{DomBackend, WebSysDomBackend};
 use crate::dom_renderer::VEventListener;
 use kagura::node::Msg;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::{Arc, Mutex};
-use std::task::{Context, Poll};
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
 
 pub mod dom_event;
 
 pub use dom_event::DomEvent;
 
-type EventPollers = Arc<Mutex<VecDeque<(Pin<Box<dom_event::DomEventPoller>>, VEventListener)>>>;
+pub struct DomEvents<B: DomBackend = WebSysDomBackend> {
+    backend: B,
+    root: B::Node,
+    created_events: HashMap<String, DomEvent>,
+    msg_queue: MsgQueue,
+    batch_is_enebale: Rc<Cell<bool>>,
+}
+
+/// Messages which are made by event handlers.
+#[derive(Clone)]
+pub struct MsgQueue {
+    state: Rc<RefCell<MsgQueueState>>,
+}
 
-pub struct DomEvents {
-    root: web_sys::EventTarget,
-    created_event_pollers: HashMap<String, DomEvent>,
-    using_event_pollers: EventPollers,
-    batch_is_enebale: Arc<Cell<bool>>,
+struct MsgQueueState {
+    msgs: Vec<Msg>,
+    waker: Option<Waker>,
 }
 
 pub struct DomEventsBatch {
-    is_enebale: Arc<Cell<bool>>,
-    event_pollers: EventPollers,
+    is_enebale: Rc<Cell<bool>>,
+    msg_queue: MsgQueue,
 }
 
 pub struct DomEventsPoller {
-    event_pollers: EventPollers,
+    msg_queue: MsgQueue,
 }
 
 impl DomEvents {
-    pub fn new(root: web_sys::EventTarget) -> Self {
+    pub fn new(root: web_sys::Node, msg_queue: MsgQueue) -> Self {
+        Self::with_backend(WebSysDomBackend::new(), root, msg_queue)
+    }
+}
+
+impl<B: DomBackend> DomEvents<B> {
+    /// Messages of handlers are pushed to `msg_queue`, which is shared with `DomRenderer`.
+    pub fn with_backend(backend: B, root: B::Node, msg_queue: MsgQueue) -> Self {
         Self {
+            backend,
             root,
-            created_event_pollers: HashMap::new(),
-            using_event_pollers: Arc::new(Mutex::new(VecDeque::new())),
-            batch_is_enebale: Arc::new(Cell::new(true)),
+            created_events: HashMap::new(),
+            msg_queue,
+            batch_is_enebale: Rc::new(Cell::new(true)),
         }
     }
 
     pub fn batch(&mut self) -> impl kagura::future_msg::Batch {
-        DomEventsBatch::new(
-            Arc::clone(&self.batch_is_enebale),
-            Arc::clone(&self.using_event_pollers),
-        )
+        DomEventsBatch::new(Rc::clone(&self.batch_is_enebale), self.msg_queue.clone())
     }
 
-    pub fn listen(&mut self, event_listeners: HashMap<String, VEventListener>) {
-        if let Ok(mut using_event_pollers) = self.using_event_pollers.try_lock() {
-            using_event_pollers.clear();
-            for (event_type, event_listener) in event_listeners {
-                let event_poller =
-                    if let Some(dom_event) = self.created_event_pollers.get(&event_type) {
-                        dom_event.poll()
-                    } else {
-                        let dom_event = DomEvent::new(&self.root, &event_type);
-                        let event_poller = dom_event.poll();
-                        self.created_event_pollers.insert(event_type, dom_event);
-                        event_poller
-                    };
-                using_event_pollers.push_back((Box::pin(event_poller), event_listener));
-            }
+    /// The queue which messages of handlers are pushed to.
+    pub fn msg_queue(&self) -> &MsgQueue {
+        &self.msg_queue
+    }
+
+    /// Sets listeners of the current tree.
+    /// Native listeners of event types which have no handler are removed.
+    pub fn listen(&mut self, mut event_listeners: HashMap<String, VEventListener>) {
+        self.created_events
+            .retain(|event_type, _| event_listeners.contains_key(event_type));
+
+        for (event_type, dom_event) in &self.created_events {
+            dom_event.set_listener(event_listeners.remove(event_type));
+        }
+
+        for (event_type, event_listener) in event_listeners {
+            let dom_event = DomEvent::new(
+                &self.backend,
+                &self.root,
+                &event_type,
+                self.msg_queue.clone(),
+            );
+            dom_event.set_listener(Some(event_listener));
+            self.created_events.insert(event_type, dom_event);
         }
     }
 }
 
-impl std::ops::Drop for DomEvents {
+impl<B: DomBackend> std::ops::Drop for DomEvents<B> {
     fn drop(&mut self) {
         self.batch_is_enebale.set(false);
     }
 }
 
+impl MsgQueue {
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(MsgQueueState {
+                msgs: vec![],
+                waker: None,
+            })),
+        }
+    }
+
+    pub fn push(&self, mut msgs: VecDeque<Msg>) {
+        let mut state = self.state.borrow_mut();
+        state.msgs.extend(msgs.drain(..));
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl std::default::Default for MsgQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DomEventsBatch {
-    pub fn new(is_enebale: Arc<Cell<bool>>, event_pollers: EventPollers) -> Self {
+    pub fn new(is_enebale: Rc<Cell<bool>>, msg_queue: MsgQueue) -> Self {
         Self {
             is_enebale,
-            event_pollers,
+            msg_queue,
         }
     }
 }
@@ -84,9 +134,7 @@ impl DomEventsBatch {
 impl kagura::future_msg::Batch for DomEventsBatch {
     fn poll(&mut self) -> Option<kagura::future_msg::Task> {
         if self.is_enebale.get() {
-            Some(Box::pin(DomEventsPoller::new(Arc::clone(
-                &self.event_pollers,
-            ))))
+            Some(Box::pin(DomEventsPoller::new(self.msg_queue.clone())))
         } else {
             None
         }
@@ -94,8 +142,8 @@ impl kagura::future_msg::Batch for DomEventsBatch {
 }
 
 impl DomEventsPoller {
-    pub fn new(event_pollers: EventPollers) -> Self {
-        Self { event_pollers }
+    pub fn new(msg_queue: MsgQueue) -> Self {
+        Self { msg_queue }
     }
 }
 
@@ -103,26 +151,12 @@ impl Future for DomEventsPoller {
     type Output = Vec<Msg>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        if let Ok(mut event_pollers) = self.event_pollers.try_lock() {
-            let mut new_event_pollers = VecDeque::new();
-            let mut output = vec![];
-            let mut is_ready = false;
-
-            for event_poller in event_pollers.iter_mut() {
-                if let Poll::Ready(e) = event_poller.0.as_mut().poll(cx) {
-                    is_ready = true;
-                    let mut msg: Vec<_> = (event_poller.1)(e).1.into();
-                    output.append(&mut msg);
-                }
-            }
-
-            event_pollers.append(&mut new_event_pollers);
-
-            if is_ready {
-                return Poll::Ready(output);
-            }
+        let mut state = self.msg_queue.state.borrow_mut();
+        if state.msgs.is_empty() {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        } else {
+            Poll::Ready(state.msgs.drain(..).collect())
         }
-
-        Poll::Pending
     }
 }
@@ -1,21 +1,26 @@
-use super::DomNode;
-use crate::DomEvents;
+use super::{Commits, DomNode};
+use crate::dom_backend::{DomBackend, WebSysDomBackend};
+use crate::dom_events::{DomEvents, MsgQueue};
+use crate::style::StyleRegistry;
 use crate::{DomRenderer, Html, HtmlRenderer};
 use kagura::node::{Msg, NodeCmd, RenderNode, UpdateNode};
 use kagura::FutureMsg;
 use std::future;
 use std::pin::Pin;
+use wasm_bindgen::JsCast;
 
 pub mod basic_dom_component;
 
 pub use basic_dom_component::BasicDomComponent;
 
-pub struct BasicDomNode {
+pub struct BasicDomNode<B: DomBackend = WebSysDomBackend> {
     dummy_state: Pin<Box<BasicDomComponent>>,
-    dom_renderer: DomRenderer,
-    dom_events: DomEvents,
+    dom_renderer: DomRenderer<B>,
+    dom_events: DomEvents<B>,
     html_renderer: HtmlRenderer<BasicDomComponent>,
     render: Box<dyn FnMut(&BasicDomComponent) -> Vec<Html>>,
+    styles: StyleRegistry,
+    commits: Commits,
     is_first_render: bool,
 }
 
@@ -24,9 +29,27 @@ impl BasicDomNode {
         entry: web_sys::Node,
         render: impl FnMut(&BasicDomComponent) -> Vec<Html> + 'static,
     ) -> Self {
+        Self::with_backend(WebSysDomBackend::new(), entry, render)
+    }
+}
+
+impl<B: DomBackend> BasicDomNode<B> {
+    pub fn with_backend(
+        backend: B,
+        entry: B::Node,
+        render: impl FnMut(&BasicDomComponent) -> Vec<Html> + 'static,
+    ) -> Self {
+        let styles = StyleRegistry::new();
+        if let Some(entry) = backend.to_r_node(&entry) {
+            let target = Self::style_target(&entry).map(|target| backend.import_r_node(target));
+            styles.set_target(&backend, target);
+        }
+
         let dummy_state = Box::pin(BasicDomComponent::new());
-        let dom_renderer = DomRenderer::new(entry.clone());
-        let dom_events = DomEvents::new(entry.into());
+        let msg_queue = MsgQueue::new();
+        let dom_renderer =
+            DomRenderer::with_backend(backend.clone(), entry.clone(), msg_queue.clone());
+        let dom_events = DomEvents::with_backend(backend, entry, msg_queue);
         let render = Box::new(render);
 
         Self {
@@ -35,27 +58,49 @@ impl BasicDomNode {
             dom_events,
             html_renderer: HtmlRenderer::new(),
             render,
+            styles,
+            commits: Commits::new(),
             is_first_render: true,
         }
     }
+
+    /// Stylesheets are appended to the shadow root which contains `entry`, or `<head>`.
+    fn style_target(entry: &web_sys::Node) -> Option<web_sys::Node> {
+        let root = entry.get_root_node();
+        if let Some(shadow_root) = root.dyn_ref::<web_sys::ShadowRoot>() {
+            Some(shadow_root.clone().into())
+        } else {
+            entry
+                .owner_document()
+                .and_then(|document| document.head())
+                .map(|head| head.into())
+        }
+    }
 }
 
-impl UpdateNode for BasicDomNode {
+impl<B: DomBackend> UpdateNode for BasicDomNode<B> {
     fn update(&mut self, msg: Msg) -> NodeCmd {
-        self.html_renderer.update(msg)
+        self.commits
+            .enter(|| self.styles.enter(|| self.html_renderer.update(msg)))
     }
 }
 
-impl RenderNode<NodeCmd> for BasicDomNode {
+impl<B: DomBackend> RenderNode<NodeCmd> for BasicDomNode<B> {
     fn render(&mut self) -> NodeCmd {
-        self.html_renderer
-            .set_children((self.render)(&self.dummy_state.as_ref()));
-        let (v_nodes, mut node_cmd) = self.html_renderer.render(&self.dummy_state);
+        let (v_nodes, mut node_cmd) = self.commits.enter(|| {
+            self.styles.enter(|| {
+                self.html_renderer
+                    .set_children((self.render)(&self.dummy_state.as_ref()));
+                self.html_renderer.render(&self.dummy_state)
+            })
+        });
         let event_listeners = self.dom_renderer.render(v_nodes);
+        self.commits.commit();
 
         for rendered_handler in event_listeners.rendered_handlers {
-            let msg = rendered_handler();
-            node_cmd.push_back(FutureMsg::Task(Box::pin(future::ready(vec![msg]))));
+            if let Some(msg) = rendered_handler() {
+                node_cmd.push_back(FutureMsg::Task(Box::pin(future::ready(vec![msg]))));
+            }
         }
 
         self.dom_events.listen(event_listeners.event_listeners);
@@ -68,4 +113,40 @@ impl RenderNode<NodeCmd> for BasicDomNode {
     }
 }
 
-impl DomNode for BasicDomNode {}
+impl<B: DomBackend> DomNode for BasicDomNode<B> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom_backend::MemoryDomBackend;
+    use crate::dom_node::committed;
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+
+    fn is_ready(future: &mut Pin<Box<impl Future<Output = ()>>>) -> bool {
+        let mut cx = Context::from_waker(Waker::noop());
+        future.as_mut().poll(&mut cx) == Poll::Ready(())
+    }
+
+    #[test]
+    fn it_resolves_committed_by_the_updating_node() {
+        let backend = MemoryDomBackend::new();
+        let mut node_a = BasicDomNode::with_backend(backend.clone(), backend.create_root(), |_| {
+            vec![Html::text("a")]
+        });
+        let mut node_b = BasicDomNode::with_backend(backend.clone(), backend.create_root(), |_| {
+            vec![Html::text("b")]
+        });
+
+        let mut committed_a = Box::pin(node_a.commits.enter(committed));
+        let mut committed_outside = Box::pin(committed());
+        assert!(!is_ready(&mut committed_a));
+
+        node_b.render();
+        assert!(!is_ready(&mut committed_a));
+        assert!(is_ready(&mut committed_outside));
+
+        node_a.render();
+        assert!(is_ready(&mut committed_a));
+    }
+}
@@ -1,7 +1,108 @@
 use kagura::node::NodeCmd;
 use kagura::node::RenderNode;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
 
 pub mod basic_dom_node;
 pub use basic_dom_node::BasicDomNode;
 
 pub trait DomNode: RenderNode<NodeCmd> {}
+
+thread_local! {
+    static CURRENT: RefCell<Vec<Commits>> = const { RefCell::new(vec![]) };
+    static DEFAULT: Commits = Commits::new();
+}
+
+/// DOM commits of a `BasicDomNode`.
+#[derive(Clone)]
+pub struct Commits {
+    state: Rc<RefCell<CommitState>>,
+}
+
+struct CommitState {
+    count: u64,
+    wakers: Vec<Waker>,
+}
+
+/// Resolved after the next DOM commit by `BasicDomNode::render`.
+pub struct Committed {
+    count: u64,
+    state: Rc<RefCell<CommitState>>,
+}
+
+/// Returns a future which is resolved when `BasicDomNode` commits the DOM next time.
+/// The future must be created before the render, e.g. in `update`.
+/// It waits for the node which is updating, or for any node outside of nodes.
+pub fn committed() -> Committed {
+    CURRENT
+        .with(|current| current.borrow().last().cloned())
+        .unwrap_or_else(|| DEFAULT.with(Commits::clone))
+        .committed()
+}
+
+impl Commits {
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(CommitState {
+                count: 0,
+                wakers: vec![],
+            })),
+        }
+    }
+
+    /// `committed` in `f` waits for commits of this.
+    pub fn enter<T>(&self, f: impl FnOnce() -> T) -> T {
+        CURRENT.with(|current| current.borrow_mut().push(self.clone()));
+        let res = f();
+        CURRENT.with(|current| current.borrow_mut().pop());
+        res
+    }
+
+    pub fn committed(&self) -> Committed {
+        Committed {
+            count: self.state.borrow().count,
+            state: Rc::clone(&self.state),
+        }
+    }
+
+    /// Resolves futures of this, and futures which are created outside of nodes.
+    pub(crate) fn commit(&self) {
+        Self::wake(&self.state);
+        DEFAULT.with(|default| Self::wake(&default.state));
+    }
+
+    fn wake(state: &RefCell<CommitState>) {
+        let wakers = {
+            let mut state = state.borrow_mut();
+            state.count += 1;
+            state.wakers.drain(..).collect::<Vec<_>>()
+        };
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl std::default::Default for Commits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Future for Committed {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        if state.count > self.count {
+            Poll::Ready(())
+        } else {
+            state.wakers.push(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::dom_backend::DomBackend;
+use std::any::Any;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// A reference to a rendered node, which is given to commands in `dom_cmd`.
+/// It is resolved when the command runs, so it may refer to a node rendered after the command is made.
+pub trait DomRef {
+    /// The referred node, or `None` if it is not rendered.
+    fn node(&self) -> Option<web_sys::Node>;
+
+    fn element<T: JsCast>(&self) -> Option<T> {
+        self.node().and_then(|node| node.dyn_into().ok())
+    }
+}
+
+/// A typed reference to an element, which is given to `Events::node_ref`.
+/// `DomRenderer` updates it when the element is created, replaced or removed.
+///
+/// ```ignore
+/// // in constructor
+/// input: NodeRef::new(),
+///
+/// // in render
+/// Html::input(Attributes::new(), Events::new().node_ref(&self.input), vec![])
+///
+/// // in update
+/// Msg::Edit => dom_cmd::focus(self.input.clone()),
+/// ```
+pub struct NodeRef<T> {
+    node: Rc<RefCell<Option<RefTarget>>>,
+    _type: PhantomData<T>,
+}
+
+/// An element which is attached to `NodeRef`.
+pub(crate) struct RefTarget {
+    /// `DomBackend::Node` of the backend which rendered the element.
+    pub raw: Box<dyn Any>,
+    pub r_node: Option<web_sys::Node>,
+}
+
+impl<T: JsCast> NodeRef<T> {
+    pub fn new() -> Self {
+        Self {
+            node: Rc::new(RefCell::new(None)),
+            _type: PhantomData,
+        }
+    }
+
+    /// The element, or `None` if it is not rendered or it is not `T`.
+    pub fn get(&self) -> Option<T> {
+        self.node
+            .borrow()
+            .as_ref()
+            .and_then(|target| target.r_node.clone()?.dyn_into().ok())
+    }
+
+    /// The element as a node of `B`, e.g. `MemoryNode` to test components without browser.
+    pub fn raw<B: DomBackend>(&self) -> Option<B::Node> {
+        self.node
+            .borrow()
+            .as_ref()
+            .and_then(|target| target.raw.downcast_ref::<B::Node>().cloned())
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.node.borrow().is_some()
+    }
+
+    pub(crate) fn cell(&self) -> Rc<RefCell<Option<RefTarget>>> {
+        Rc::clone(&self.node)
+    }
+}
+
+impl<T> Clone for NodeRef<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: Rc::clone(&self.node),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T: JsCast> std::default::Default for NodeRef<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> std::fmt::Debug for NodeRef<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NodeRef({})", self.node.borrow().is_some())
+    }
+}
+
+impl<T: JsCast> DomRef for NodeRef<T> {
+    fn node(&self) -> Option<web_sys::Node> {
+        self.node
+            .borrow()
+            .as_ref()
+            .and_then(|target| target.r_node.clone())
+    }
+}
+
+impl DomRef for web_sys::Node {
+    fn node(&self) -> Option<web_sys::Node> {
+        Some(self.clone())
+    }
+}
+
+impl DomRef for web_sys::Element {
+    fn node(&self) -> Option<web_sys::Node> {
+        Some(self.clone().into())
+    }
+}
+
+impl DomRef for web_sys::HtmlElement {
+    fn node(&self) -> Option<web_sys::Node> {
+        Some(self.clone().into())
+    }
+}
+
+impl<T: DomRef> DomRef for Option<T> {
+    fn node(&self) -> Option<web_sys::Node> {
+        self.as_ref().and_then(T::node)
+    }
+}
@@ -0,0 +1,61 @@
+use super::{DomRenderer, VRenderedHandler};
+use crate::dom_backend::DomBackend;
+use crate::dom_events::{DomEvents, MsgQueue};
+use crate::html::PortalTarget;
+use crate::VNode;
+use std::collections::VecDeque;
+
+/// Children of `Html::portal`, which are rendered into a container appended to the target.
+/// It has its own renderer and listeners, and the container is removed when it is dropped.
+pub struct DomPortal<B: DomBackend> {
+    backend: B,
+    target: B::Node,
+    container: B::Node,
+    dom_renderer: DomRenderer<B>,
+    dom_events: DomEvents<B>,
+}
+
+impl<B: DomBackend> DomPortal<B> {
+    /// Messages of handlers are pushed to `msg_queue` of the parent.
+    /// Returns `None` if `target` is neither a node of `B` nor a `web_sys::Node`.
+    pub fn new(backend: &B, target: &PortalTarget, msg_queue: &MsgQueue) -> Option<Self> {
+        let target = target.get::<B::Node>().or_else(|| {
+            target
+                .get::<web_sys::Node>()
+                .map(|target| backend.import_r_node(target))
+        })?;
+        let container = backend.create_element("div");
+        backend.set_style_property(&container, "display", "contents");
+        backend.insert_before(&target, &container, None);
+
+        let dom_renderer =
+            DomRenderer::with_backend(backend.clone(), container.clone(), msg_queue.clone());
+        let dom_events =
+            DomEvents::with_backend(backend.clone(), container.clone(), msg_queue.clone());
+
+        Some(Self {
+            backend: backend.clone(),
+            target,
+            container,
+            dom_renderer,
+            dom_events,
+        })
+    }
+
+    pub fn render(&mut self, children: VecDeque<VNode>) -> Vec<VRenderedHandler> {
+        let event_listeners = self.dom_renderer.render(children);
+        self.dom_events.listen(event_listeners.event_listeners);
+        event_listeners.rendered_handlers
+    }
+
+    /// Removes children so that `NodeRef`s in them are detached.
+    pub fn unmount(mut self) -> Vec<VRenderedHandler> {
+        self.render(VecDeque::new())
+    }
+}
+
+impl<B: DomBackend> std::ops::Drop for DomPortal<B> {
+    fn drop(&mut self) {
+        self.backend.remove_child(&self.target, &self.container);
+    }
+}
@@ -1,35 +1,79 @@
-use crate::v_node::v_element::{VAttributeValues, VAttributes, VEvent, VEventHandler, VEvents};
-use crate::v_node::{VElement, VText};
+use crate::dom_backend::{DomBackend, DomEventListenerOptions, WebSysDomBackend};
+use crate::dom_events::{DomEvent, MsgQueue};
+use crate::v_node::v_element::{
+    VAttributeValues, VAttributes, VDirectListeners, VEvent, VEventHandler, VEvents,
+    VListenerOptions, VPropertyDefaults,
+};
+use crate::v_node::v_event_target::{BackendEvent, VEventSource};
+use crate::v_node::{VElement, VPortal, VText};
 use crate::VNode;
 use kagura::node::Msg;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::rc::Rc;
-use wasm_bindgen::JsCast;
 
-pub type VEventListener = Box<dyn FnMut(web_sys::Event) -> (bool, VecDeque<Msg>)>;
-pub type VRenderedHandler = Box<dyn FnOnce() -> Msg>;
+mod dom_portal;
+
+use dom_portal::DomPortal;
+
+/// It is called re-entrantly if a handler dispatches another event of the same type.
+pub type VEventListener = Box<dyn Fn(web_sys::Event) -> (bool, VecDeque<Msg>)>;
+/// Returns `None` if no message is sent.
+pub type VRenderedHandler = Box<dyn FnOnce() -> Option<Msg>>;
 
 pub struct VEventListeners {
     pub event_listeners: HashMap<String, VEventListener>,
     pub rendered_handlers: Vec<VRenderedHandler>,
 }
 
-pub struct DomRenderer {
-    root: web_sys::Node,
+pub struct DomRenderer<B: DomBackend = WebSysDomBackend> {
+    root: B::Node,
     prevs: VecDeque<VNode>,
-    document: web_sys::Document,
+    backend: B,
+    msg_queue: MsgQueue,
+    composition: RefCell<Option<Composition<B>>>,
+    /// The first created element with `autofocus`, which is focused after it is inserted.
+    autofocus: RefCell<Option<B::Node>>,
+    portals: RefCell<HashMap<usize, DomPortal<B>>>,
+    portal_count: Cell<usize>,
 }
 
+/// Tracks an element which is composing text with IME.
+struct Composition<B: DomBackend> {
+    target: Rc<RefCell<Option<B::Node>>>,
+    /// `value` which is rendered while composing. It is written when the composition ends.
+    deferred: Rc<RefCell<Option<DeferredValue<B>>>>,
+    listeners: Vec<(&'static str, B::EventListenerHandle)>,
+}
+
+type DeferredValue<B> = (<B as DomBackend>::Node, String);
+
 impl DomRenderer {
-    pub fn new(root: web_sys::Node) -> Self {
+    pub fn new(root: web_sys::Node, msg_queue: MsgQueue) -> Self {
+        Self::with_backend(WebSysDomBackend::new(), root, msg_queue)
+    }
+}
+
+impl<B: DomBackend> DomRenderer<B> {
+    /// Messages of direct listeners are pushed to `msg_queue`.
+    /// It should be shared with `DomEvents` of the same root.
+    pub fn with_backend(backend: B, root: B::Node, msg_queue: MsgQueue) -> Self {
         Self {
             root,
             prevs: VecDeque::new(),
-            document: web_sys::window().unwrap().document().unwrap(),
+            backend,
+            msg_queue,
+            composition: RefCell::new(None),
+            autofocus: RefCell::new(None),
+            portals: RefCell::new(HashMap::new()),
+            portal_count: Cell::new(0),
         }
     }
 
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
     pub fn render(&mut self, nows: VecDeque<VNode>) -> VEventListeners {
         let rendered_nows = nows
             .iter()
@@ -38,6 +82,11 @@ impl DomRenderer {
         let preves = self.prevs.drain(..).collect::<VecDeque<_>>();
         let event_listeners = self.render_nodes(preves, nows, &self.root);
         self.prevs = rendered_nows;
+
+        if let Some(autofocus) = self.autofocus.borrow_mut().take() {
+            self.backend.focus(&autofocus);
+        }
+
         event_listeners
     }
 
@@ -45,22 +94,9 @@ impl DomRenderer {
         &self,
         prevs: VecDeque<VNode>,
         nows: VecDeque<VNode>,
-        raw_parent: &web_sys::Node,
+        raw_parent: &B::Node,
     ) -> VEventListeners {
-        let mut raws = {
-            let raws = raw_parent.child_nodes();
-            let mut buf = VecDeque::new();
-            let raws_len = raws.length();
-            for i in 0..raws_len {
-                if let Some(raw) = raws.get(i) {
-                    let node_type = raw.node_type();
-                    if node_type == 1 || node_type == 3 {
-                        buf.push_back(raw);
-                    }
-                }
-            }
-            buf
-        };
+        let mut raws = self.backend.child_nodes(raw_parent);
 
         let mixeds = crate::util::mix(prevs, nows, Self::compare_nodes, 5.0, 10.0, 1.0);
 
@@ -84,13 +120,15 @@ impl DomRenderer {
                             raw_idx += 1;
                         }
                     }
-                    crate::util::mix::Edit::Remove(..) => {
+                    crate::util::mix::Edit::Remove(prev) => {
                         if let Some(raw_remove) = raws.remove(raw_idx) {
-                            let _ = raw_parent.remove_child(&raw_remove);
+                            self.unmount_node(&prev, &raw_remove, &mut rendereds);
+                            self.backend.remove_child(raw_parent, &raw_remove);
                         }
                     }
-                    crate::util::mix::Edit::Replace(_, now) => {
+                    crate::util::mix::Edit::Replace(prev, now) => {
                         if let Some(raw) = raws.get(raw_idx) {
+                            self.unmount_node(&prev, raw, &mut rendereds);
                             let event_lsiteners = self.replace_node(now, &raw_parent, &raw);
                             let mut rendered_handlers =
                                 Self::append_events(&mut events, event_lsiteners);
@@ -106,14 +144,15 @@ impl DomRenderer {
 
         let event_listeners = events.into_iter().fold(
             HashMap::new(),
-            |mut event_listeners, (event_type, mut event_listener_list)| {
+            |mut event_listeners, (event_type, event_listener_list)| {
+                let backend = self.backend.clone();
                 event_listeners.insert(
                     event_type,
                     Box::new(move |e: web_sys::Event| {
                         let mut msgs = VecDeque::new();
                         let mut stop_propagation = false;
-                        for event_listener in &mut event_listener_list {
-                            let mut res = event_listener(e.clone());
+                        for event_listener in &event_listener_list {
+                            let mut res = event_listener(backend.clone_event(&e));
                             stop_propagation = stop_propagation | res.0;
                             msgs.append(&mut res.1);
                         }
@@ -153,6 +192,7 @@ impl DomRenderer {
             }
             (VNode::VText(..), VNode::VText(..)) => true,
             (VNode::RNode(prev), VNode::RNode(now)) => prev.is_same_node(Some(&now)),
+            (VNode::VPortal(prev), VNode::VPortal(now)) => prev.target.is_same(&now.target),
             _ => false,
         }
     }
@@ -160,31 +200,49 @@ impl DomRenderer {
     fn append_node(
         &self,
         now: VNode,
-        raw_parent: &web_sys::Node,
-        raw_after: Option<&web_sys::Node>,
+        raw_parent: &B::Node,
+        raw_after: Option<&B::Node>,
     ) -> VEventListeners {
-        let (event_listeners, raw) = match now {
-            VNode::VElement(now) => self.create_element(now, &VEvents::new()),
-            VNode::VText(now) => {
-                let raw = self.document.create_text_node(&now.text);
-                (VEventListeners::new(), raw.into())
-            }
-            VNode::RNode(now_raw) => (VEventListeners::new(), now_raw),
-        };
+        let (event_listeners, raw) = self.create_node(now);
+        self.backend.insert_before(raw_parent, &raw, raw_after);
+        event_listeners
+    }
 
-        if let Err(err) = raw_parent.insert_before(&raw, raw_after) {
-            web_sys::console::log_1(&err);
-            panic!();
-        }
+    /// Clears `NodeRef`s and removes portals in a removed node and its descendants.
+    fn unmount_node(&self, prev: &VNode, raw: &B::Node, rendereds: &mut Vec<VRenderedHandler>) {
+        match prev {
+            VNode::VElement(prev) => {
+                rendereds.extend(
+                    prev.events
+                        .node_refs
+                        .iter()
+                        .filter_map(|node_ref| node_ref.detach(&self.backend, raw)),
+                );
 
-        event_listeners
+                let raw_children = self.backend.child_nodes(raw);
+                for (prev_child, raw_child) in prev.children.iter().zip(raw_children.iter()) {
+                    self.unmount_node(prev_child, raw_child, rendereds);
+                }
+            }
+            VNode::VPortal(prev) => {
+                let portal = prev
+                    .id
+                    .get()
+                    .and_then(|id| self.portals.borrow_mut().remove(&id));
+                if let Some(portal) = portal {
+                    rendereds.append(&mut portal.unmount());
+                }
+            }
+            VNode::VText(..) | VNode::RNode(..) => {}
+        }
     }
 
-    fn keep_node(&self, prev: VNode, now: VNode, raw: &web_sys::Node) -> VEventListeners {
+    fn keep_node(&self, prev: VNode, now: VNode, raw: &B::Node) -> VEventListeners {
         match (prev, now) {
             (VNode::VElement(prev), VNode::VElement(now)) => self.keep_element(prev, now, raw),
             (VNode::VText(prev), VNode::VText(now)) => self.keep_text(prev, now, raw),
             (VNode::RNode(..), VNode::RNode(..)) => VEventListeners::new(),
+            (VNode::VPortal(prev), VNode::VPortal(now)) => self.keep_portal(prev, now),
             (prev, now) => {
                 web_sys::console::log_1(&wasm_bindgen::JsValue::from(format!(
                     "{:?} and {:?} is not same node",
@@ -198,110 +256,335 @@ impl DomRenderer {
     fn replace_node(
         &self,
         now: VNode,
-        raw_parent: &web_sys::Node,
-        prev_raw: &web_sys::Node,
+        raw_parent: &B::Node,
+        prev_raw: &B::Node,
     ) -> VEventListeners {
-        let (event_listeners, now_raw) = match now {
+        let (event_listeners, now_raw) = self.create_node(now);
+        self.backend.replace_child(raw_parent, &now_raw, prev_raw);
+        event_listeners
+    }
+
+    fn create_node(&self, now: VNode) -> (VEventListeners, B::Node) {
+        match now {
             VNode::VElement(now) => self.create_element(now, &VEvents::new()),
             VNode::VText(now) => {
-                let raw = self.document.create_text_node(&now.text);
-                (VEventListeners::new(), raw.into())
+                let raw = self.backend.create_text_node(&now.text);
+                (VEventListeners::new(), raw)
             }
-            VNode::RNode(now_raw) => (VEventListeners::new(), now_raw),
-        };
+            VNode::RNode(now_raw) => (VEventListeners::new(), self.backend.import_r_node(now_raw)),
+            VNode::VPortal(now) => self.create_portal(now),
+        }
+    }
 
-        let _ = raw_parent.replace_child(&now_raw, &prev_raw);
+    /// A portal leaves an empty text node in the parent, which keeps positions of siblings.
+    fn create_portal(&self, now: VPortal) -> (VEventListeners, B::Node) {
+        let id = self.portal_count.get();
+        self.portal_count.set(id + 1);
+        now.id.set(Some(id));
 
-        event_listeners
-    }
+        let rendered_handlers = match DomPortal::new(&self.backend, &now.target, &self.msg_queue) {
+            Some(mut portal) => {
+                let rendered_handlers = portal.render(now.children);
+                self.portals.borrow_mut().insert(id, portal);
+                rendered_handlers
+            }
+            None => vec![],
+        };
 
-    fn keep_element(&self, prev: VElement, now: VElement, raw: &web_sys::Node) -> VEventListeners {
-        if let Some(raw) = raw.dyn_ref::<web_sys::Element>() {
-            let child_event_listeners = self.render_nodes(prev.children, now.children, &raw);
+        let event_listeners = VEventListeners {
+            event_listeners: HashMap::new(),
+            rendered_handlers,
+        };
+        (event_listeners, self.backend.create_text_node(""))
+    }
 
-            Self::update_attributes(&prev.attributes, &now.attributes, &raw);
+    fn keep_portal(&self, prev: VPortal, now: VPortal) -> VEventListeners {
+        now.id.set(prev.id.get());
 
-            let event_listeners =
-                Self::create_event_listeners(now.events, child_event_listeners, raw, &prev.events);
+        let rendered_handlers = prev
+            .id
+            .get()
+            .and_then(|id| {
+                let mut portals = self.portals.borrow_mut();
+                portals
+                    .get_mut(&id)
+                    .map(|portal| portal.render(now.children))
+            })
+            .unwrap_or_default();
 
-            event_listeners
-        } else {
-            web_sys::console::log_1(&wasm_bindgen::JsValue::from(
-                "Can not dy_ref as web_sys::Element",
-            ));
-            unreachable!();
+        VEventListeners {
+            event_listeners: HashMap::new(),
+            rendered_handlers,
         }
     }
 
-    fn update_attributes(prev: &VAttributes, now: &VAttributes, raw: &web_sys::Element) {
+    fn keep_element(&self, prev: VElement, now: VElement, raw: &B::Node) -> VEventListeners {
+        let child_event_listeners = self.render_nodes(prev.children, now.children, raw);
+
+        now.property_defaults.replace(prev.property_defaults.take());
+        self.update_attributes(
+            &prev.attributes,
+            &now.attributes,
+            raw,
+            &now.property_defaults,
+        );
+
+        self.create_event_listeners(now.events, child_event_listeners, raw, &prev.events)
+    }
+
+    fn update_attributes(
+        &self,
+        prev: &VAttributes,
+        now: &VAttributes,
+        raw: &B::Node,
+        property_defaults: &VPropertyDefaults,
+    ) {
         for (attr_name, now_values) in now {
-            if let Some(prev_values) = prev.get(attr_name) {
+            if now_values.property().is_some() || attr_name == "value" {
+                // compares with the live value, because users can change it.
+                self.set_attribute(attr_name, now_values, raw, property_defaults);
+            } else if attr_name == "class" {
+                self.update_class(prev.get(attr_name), Some(now_values), raw);
+            } else if attr_name == "style" {
+                self.update_style(prev.get(attr_name), Some(now_values), raw);
+            } else if let Some(prev_values) = prev.get(attr_name) {
                 if *prev_values != *now_values {
-                    Self::set_attribute(attr_name, &now_values, &raw);
+                    self.set_attribute(attr_name, now_values, raw, property_defaults);
                 }
             } else {
-                Self::set_attribute(attr_name, &now_values, &raw);
+                self.set_attribute(attr_name, now_values, raw, property_defaults);
             }
         }
 
-        for (attr_name, _) in prev {
+        for (attr_name, prev_values) in prev {
             if !now.contains_key(attr_name) {
-                let _ = raw.remove_attribute(attr_name);
+                if prev_values.property().is_some() {
+                    let default = property_defaults.borrow_mut().remove(attr_name);
+                    if let Some(default) = default {
+                        self.backend.set_property(raw, attr_name, default);
+                    }
+                } else if attr_name == "class" {
+                    self.update_class(Some(prev_values), None, raw);
+                } else if attr_name == "style" {
+                    self.update_style(Some(prev_values), None, raw);
+                } else if let Some(namespace) = &prev_values.namespace {
+                    self.backend.remove_attribute_ns(raw, namespace, attr_name);
+                } else {
+                    self.backend.remove_attribute(raw, attr_name);
+                }
             }
         }
     }
 
-    fn set_attribute(attr_name: &String, now: &VAttributeValues, raw: &web_sys::Element) {
-        if attr_name == "value" {
-            if let Some(raw) = raw.dyn_ref::<web_sys::HtmlInputElement>() {
-                raw.set_value(now.to_string().as_str());
-            } else if let Some(raw) = raw.dyn_ref::<web_sys::HtmlTextAreaElement>() {
-                raw.set_value(now.to_string().as_str());
-            } else {
-                let _ = raw.set_attribute("value", now.to_string().as_str());
+    /// Updates `class` by each class name, so classes which are added by others are kept.
+    fn update_class(
+        &self,
+        prev: Option<&VAttributeValues>,
+        now: Option<&VAttributeValues>,
+        raw: &B::Node,
+    ) {
+        let prev = prev.map(|prev| prev.class_names()).unwrap_or_default();
+        let now = now.map(|now| now.class_names()).unwrap_or_default();
+
+        for class_name in &prev {
+            if !now.contains(class_name) {
+                self.backend.remove_class(raw, class_name);
             }
+        }
+
+        for class_name in &now {
+            if !prev.contains(class_name) {
+                self.backend.add_class(raw, class_name);
+            }
+        }
+    }
+
+    /// Updates `style` by each property, so styles which are set by others are kept.
+    fn update_style(
+        &self,
+        prev: Option<&VAttributeValues>,
+        now: Option<&VAttributeValues>,
+        raw: &B::Node,
+    ) {
+        let prev = prev.map(|prev| prev.style_properties()).unwrap_or_default();
+        let now = now.map(|now| now.style_properties()).unwrap_or_default();
+
+        for (name, _) in &prev {
+            if !now.iter().any(|(n, _)| n == name) {
+                self.backend.remove_style_property(raw, name);
+            }
+        }
+
+        for (name, value) in &now {
+            if !prev.iter().any(|(n, v)| n == name && v == value) {
+                self.backend.set_style_property(raw, name, value);
+            }
+        }
+    }
+
+    fn set_attribute(
+        &self,
+        attr_name: &String,
+        now: &VAttributeValues,
+        raw: &B::Node,
+        property_defaults: &VPropertyDefaults,
+    ) {
+        if let Some(value) = now.property() {
+            if !self.backend.is_property(raw, attr_name, value) {
+                property_defaults
+                    .borrow_mut()
+                    .entry(attr_name.clone())
+                    .or_insert_with(|| self.backend.get_property(raw, attr_name));
+                self.backend.set_property(raw, attr_name, Rc::clone(value));
+            }
+        } else if attr_name == "value" {
+            // updates while composing break IME, so they are deferred to `compositionend`.
+            let value = now.to_string();
+            if !self.defer_value(raw, &value) {
+                Self::write_value(&self.backend, raw, &value);
+            }
+        } else if let Some(namespace) = &now.namespace {
+            self.backend
+                .set_attribute_ns(raw, namespace, attr_name, now.to_string().as_str());
         } else {
-            let _ = raw.set_attribute(attr_name, now.to_string().as_str());
+            self.backend
+                .set_attribute(raw, attr_name, now.to_string().as_str());
+        }
+    }
+
+    /// Keeps `value` if `raw` is composing, and returns whether it is kept.
+    fn defer_value(&self, raw: &B::Node, value: &str) -> bool {
+        let mut composition = self.composition.borrow_mut();
+        let composition = composition.get_or_insert_with(|| self.listen_composition());
+        let is_composing = composition
+            .target
+            .borrow()
+            .as_ref()
+            .map(|target| self.backend.contains(raw, target))
+            .unwrap_or(false);
+        if is_composing {
+            *composition.deferred.borrow_mut() = Some((raw.clone(), String::from(value)));
         }
+        is_composing
     }
 
-    fn keep_text(&self, prev: VText, now: VText, raw: &web_sys::Node) -> VEventListeners {
-        if let Some(raw) = raw.dyn_ref::<web_sys::CharacterData>() {
-            let _ = raw.replace_data(0, prev.text.len() as u32, &now.text);
+    /// Writes `value` only if the live value differs, so that the caret is not moved.
+    fn write_value(backend: &B, raw: &B::Node, value: &str) {
+        if backend.get_value(raw).as_deref() != Some(value) {
+            backend.set_value(raw, value);
+        }
+    }
+
+    fn listen_composition(&self) -> Composition<B> {
+        let target = Rc::new(RefCell::new(None));
+        let deferred = Rc::new(RefCell::new(None::<DeferredValue<B>>));
+        let options = DomEventListenerOptions {
+            capture: true,
+            ..Default::default()
+        };
+
+        let start = self.backend.add_event_listener(
+            &self.root,
+            "compositionstart",
+            Box::new({
+                let backend = self.backend.clone();
+                let target = Rc::clone(&target);
+                move |e| *target.borrow_mut() = backend.event_target(&e)
+            }),
+            options,
+        );
+        let end = self.backend.add_event_listener(
+            &self.root,
+            "compositionend",
+            Box::new({
+                let backend = self.backend.clone();
+                let target = Rc::clone(&target);
+                let deferred = Rc::clone(&deferred);
+                move |_| {
+                    *target.borrow_mut() = None;
+                    let deferred = deferred.borrow_mut().take();
+                    if let Some((raw, value)) = deferred {
+                        Self::write_value(&backend, &raw, &value);
+                    }
+                }
+            }),
+            options,
+        );
+
+        Composition {
+            target,
+            deferred,
+            listeners: vec![("compositionstart", start), ("compositionend", end)],
         }
+    }
+
+    fn keep_text(&self, _prev: VText, now: VText, raw: &B::Node) -> VEventListeners {
+        self.backend.set_text(raw, &now.text);
         VEventListeners::new()
     }
 
-    fn create_element(&self, now: VElement, prev: &VEvents) -> (VEventListeners, web_sys::Node) {
+    fn create_element(&self, now: VElement, prev: &VEvents) -> (VEventListeners, B::Node) {
         let raw_element = if let Some(namespace) = now.namespace {
-            self.document
-                .create_element_ns(Some(namespace.as_str()), &now.tag_name)
-                .unwrap()
+            self.backend
+                .create_element_ns(namespace.as_str(), &now.tag_name)
         } else {
-            self.document.create_element(&now.tag_name).unwrap()
+            self.backend.create_element(&now.tag_name)
         };
 
         let child_event_listeners = self.render_nodes(VecDeque::new(), now.children, &raw_element);
 
+        if now.attributes.contains_key("autofocus") {
+            self.autofocus
+                .borrow_mut()
+                .get_or_insert_with(|| raw_element.clone());
+        }
+
         for (attr_name, attr_values) in now.attributes {
-            Self::set_attribute(&attr_name, &attr_values, &raw_element);
+            self.set_attribute(
+                &attr_name,
+                &attr_values,
+                &raw_element,
+                &now.property_defaults,
+            );
         }
 
         let event_listeners =
-            Self::create_event_listeners(now.events, child_event_listeners, &raw_element, &prev);
+            self.create_event_listeners(now.events, child_event_listeners, &raw_element, prev);
 
-        (event_listeners, raw_element.into())
+        (event_listeners, raw_element)
     }
 
     fn create_event_listeners(
+        &self,
         events: VEvents,
         mut child_event_listeners: VEventListeners,
-        raw: &web_sys::Node,
+        raw: &B::Node,
         prev: &VEvents,
     ) -> VEventListeners {
         let mut event_listeners = HashMap::new();
+        let directs = events.directs.clone();
         for (event_type, event_handlers) in events.events {
-            let mut child_event_listener = if let Some(child_event_listener) =
+            if let Some(options) = event_handlers.direct {
+                for (capture, event_handlers) in [
+                    (true, event_handlers.captures),
+                    (false, event_handlers.bubbles),
+                ] {
+                    if !event_handlers.is_empty() {
+                        let listener = self.create_direct_listener(
+                            &prev.directs,
+                            raw,
+                            &event_type,
+                            capture,
+                            options,
+                            event_handlers,
+                        );
+                        directs.insert(event_type.clone(), capture, options, listener);
+                    }
+                }
+                continue;
+            }
+
+            let child_event_listener = if let Some(child_event_listener) =
                 child_event_listeners.event_listeners.remove(&event_type)
             {
                 child_event_listener
@@ -309,24 +592,21 @@ impl DomRenderer {
                 Box::new(|_e: web_sys::Event| (false, VecDeque::new()))
             };
             let raw = raw.clone();
-            let mut event_handlers = Some(event_handlers);
+            let backend = self.backend.clone();
+            let captures = Self::share_handlers(event_handlers.captures);
+            let bubbles = Self::share_handlers(event_handlers.bubbles);
             event_listeners.insert(
                 event_type,
                 Box::new(move |e: web_sys::Event| {
-                    if raw.contains(
-                        e.target()
-                            .as_ref()
-                            .and_then(|target| target.dyn_ref::<web_sys::Node>()),
-                    ) {
-                        let (captures, bubbles) = event_handlers
-                            .take()
-                            .map(|eh| (Some(eh.captures), Some(eh.bubbles)))
-                            .unwrap_or((None, None));
-
+                    let is_target = backend
+                        .event_target(&e)
+                        .map(|target| backend.contains(&raw, &target))
+                        .unwrap_or(false);
+                    if is_target {
                         let mut msgs = VecDeque::new();
-                        if let Some(captures) = captures {
+                        if !captures.is_empty() {
                             let (stop_propagation, mut additional_msgs) =
-                                Self::attach_events(&e, captures);
+                                Self::attach_events(&backend, &e, &captures);
                             msgs.append(&mut additional_msgs);
 
                             if stop_propagation {
@@ -335,16 +615,16 @@ impl DomRenderer {
                         }
 
                         let (stop_propagation, mut additional_msgs) =
-                            child_event_listener(e.clone());
+                            child_event_listener(backend.clone_event(&e));
                         msgs.append(&mut additional_msgs);
 
                         if stop_propagation {
                             return (stop_propagation, msgs);
                         }
 
-                        if let Some(bubbles) = bubbles {
+                        if !bubbles.is_empty() {
                             let (stop_propagation, mut additional_msgs) =
-                                Self::attach_events(&e, bubbles);
+                                Self::attach_events(&backend, &e, &bubbles);
                             msgs.append(&mut additional_msgs);
 
                             if stop_propagation {
@@ -363,6 +643,8 @@ impl DomRenderer {
             event_listeners.insert(event_type, event_listener);
         }
 
+        prev.directs.clear();
+
         let mut rendered_handlers = child_event_listeners.rendered_handlers;
         let prev_targets = prev
             .refers
@@ -372,34 +654,114 @@ impl DomRenderer {
 
         for mut refer in events.refers {
             if !prev_targets.contains(&refer.target) {
-                if let Some(handler) = refer.take() {
-                    let raw = raw.clone();
-                    rendered_handlers.push(Box::new(move || handler(raw)));
+                if let (Some(handler), Some(raw)) = (refer.take(), self.backend.to_r_node(raw)) {
+                    rendered_handlers.push(Box::new(move || Some(handler(raw))));
                 }
             }
         }
 
+        for prev_node_ref in &prev.node_refs {
+            let is_kept = events
+                .node_refs
+                .iter()
+                .any(|node_ref| node_ref.is_same_ref(prev_node_ref));
+            if !is_kept {
+                rendered_handlers.extend(prev_node_ref.detach(&self.backend, raw));
+            }
+        }
+
+        for mut node_ref in events.node_refs {
+            rendered_handlers.extend(node_ref.attach(&self.backend, raw));
+        }
+
         VEventListeners {
             event_listeners,
             rendered_handlers,
         }
     }
 
+    /// Reuses a listener of the previous element if it has same options.
+    fn create_direct_listener(
+        &self,
+        prev_directs: &VDirectListeners,
+        raw: &B::Node,
+        event_type: &str,
+        capture: bool,
+        options: VListenerOptions,
+        event_handlers: Vec<VEventHandler>,
+    ) -> DomEvent {
+        let listener = match prev_directs.remove(event_type, capture) {
+            Some((prev_options, listener)) if prev_options == options => listener,
+            _ => DomEvent::with_options(
+                &self.backend,
+                raw,
+                event_type,
+                DomEventListenerOptions {
+                    capture,
+                    passive: options.passive,
+                    once: options.once,
+                },
+                self.msg_queue.clone(),
+            ),
+        };
+
+        let backend = self.backend.clone();
+        let event_handlers = Self::share_handlers(event_handlers);
+        listener.set_listener(Some(Box::new(move |e: web_sys::Event| {
+            Self::attach_events(&backend, &e, &event_handlers)
+        })));
+
+        listener
+    }
+
+    fn share_handlers(event_handlers: Vec<VEventHandler>) -> Vec<RefCell<VEventHandler>> {
+        event_handlers.into_iter().map(RefCell::new).collect()
+    }
+
     fn attach_events(
+        backend: &B,
         e: &web_sys::Event,
-        event_handlers: Vec<VEventHandler>,
+        event_handlers: &[RefCell<VEventHandler>],
     ) -> (bool, VecDeque<Msg>) {
         let mut msgs = VecDeque::new();
         let stop_propagation = Rc::new(Cell::new(false));
+        let stop_immediate_propagation = Rc::new(Cell::new(false));
+        let source: Rc<dyn VEventSource> =
+            Rc::new(BackendEvent::new(backend.clone(), backend.clone_event(e)));
         for event_handler in event_handlers {
-            let v_event = VEvent::new(e.clone(), Rc::clone(&stop_propagation));
-            let msg = event_handler(v_event);
-            msgs.push_back(msg);
+            if stop_immediate_propagation.get() {
+                break;
+            }
+            // a handler which dispatches a nested event is running, so it is skipped for the nested one.
+            let mut event_handler = match event_handler.try_borrow_mut() {
+                Ok(event_handler) => event_handler,
+                Err(_) => continue,
+            };
+            let v_event = VEvent::new(
+                backend.clone_event(e),
+                Rc::clone(&stop_propagation),
+                Rc::clone(&stop_immediate_propagation),
+            )
+            .with_source(Rc::clone(&source));
+            if let Some(msg) = (*event_handler)(v_event) {
+                msgs.push_back(msg);
+            }
         }
         (stop_propagation.get(), msgs)
     }
 }
 
+impl<B: DomBackend> std::ops::Drop for DomRenderer<B> {
+    fn drop(&mut self) {
+        if let Some(composition) = self.composition.borrow_mut().take() {
+            for (event_type, listener) in composition.listeners {
+                self.backend
+                    .remove_event_listener(&self.root, event_type, listener, true);
+            }
+        }
+    }
+}
+
 impl VEventListeners {
     pub fn new() -> Self {
         Self {
@@ -408,3 +770,898 @@ impl VEventListeners {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom_backend::memory_dom_backend::Mutation;
+    use crate::dom_backend::MemoryDomBackend;
+    use crate::dom_node::BasicDomNode;
+    use crate::html::html_element::{Attributes, Events};
+    use crate::html::PortalTarget;
+    use crate::Html;
+    use kagura::node::RenderNode;
+    use wasm_bindgen::JsValue;
+
+    fn list(items: &[&str]) -> Vec<Html> {
+        vec![Html::ul(
+            Attributes::new().class("list"),
+            Events::new(),
+            items
+                .iter()
+                .map(|item| Html::li(Attributes::new(), Events::new(), vec![Html::text(*item)]))
+                .collect(),
+        )]
+    }
+
+    #[test]
+    fn it_renders_html() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let mut node =
+            BasicDomNode::with_backend(backend.clone(), root.clone(), |_| list(&["a", "b<c"]));
+        node.render();
+
+        assert_eq!(
+            root.inner_html(),
+            r#"<ul class="list"><li>a</li><li>b&lt;c</li></ul>"#
+        );
+        assert_eq!(
+            backend.count_mutations(|m| matches!(m, Mutation::CreateElement { .. })),
+            3
+        );
+    }
+
+    #[test]
+    fn it_keeps_same_elements() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let items = Rc::new(Cell::new(["a", "b"]));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let items = Rc::clone(&items);
+            move |_| list(&items.get())
+        });
+        node.render();
+        let li = root.children()[0].children()[1].clone();
+
+        backend.clear_mutations();
+        items.set(["a", "c"]);
+        node.render();
+
+        assert_eq!(
+            root.inner_html(),
+            r#"<ul class="list"><li>a</li><li>c</li></ul>"#
+        );
+        assert!(root.children()[0].children()[1] == li);
+        assert_eq!(
+            backend.count_mutations(|m| matches!(
+                m,
+                Mutation::CreateElement { .. }
+                    | Mutation::CreateTextNode { .. }
+                    | Mutation::InsertBefore
+                    | Mutation::ReplaceChild
+                    | Mutation::RemoveChild
+                    | Mutation::SetAttribute { .. }
+            )),
+            0
+        );
+    }
+
+    #[test]
+    fn it_appends_and_removes_nodes() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let items = Rc::new(Cell::new(&["a", "b"][..]));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let items = Rc::clone(&items);
+            move |_| list(items.get())
+        });
+        node.render();
+
+        backend.clear_mutations();
+        items.set(&["a", "b", "c"]);
+        node.render();
+        assert_eq!(
+            root.inner_html(),
+            r#"<ul class="list"><li>a</li><li>b</li><li>c</li></ul>"#
+        );
+        assert_eq!(
+            backend.count_mutations(|m| **m == Mutation::InsertBefore),
+            2
+        );
+
+        backend.clear_mutations();
+        items.set(&["a"]);
+        node.render();
+        assert_eq!(root.inner_html(), r#"<ul class="list"><li>a</li></ul>"#);
+        assert_eq!(backend.count_mutations(|m| **m == Mutation::RemoveChild), 2);
+    }
+
+    #[test]
+    fn it_focuses_autofocus_elements_after_insertion() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), |_| {
+            vec![Html::input(
+                Attributes::new().autofocus(true),
+                Events::new(),
+                vec![],
+            )]
+        });
+        node.render();
+
+        let mutations = backend.mutations();
+        assert_eq!(mutations.last(), Some(&Mutation::Focus));
+        assert_eq!(backend.count_mutations(|m| **m == Mutation::Focus), 1);
+
+        backend.clear_mutations();
+        node.render();
+        assert_eq!(backend.count_mutations(|m| **m == Mutation::Focus), 0);
+    }
+
+    #[test]
+    fn it_sets_attributes_in_order() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), |_| {
+            vec![Html::div(
+                Attributes::new()
+                    .string("z", "1")
+                    .class("a")
+                    .string("m", "2")
+                    .class("b"),
+                Events::new(),
+                vec![],
+            )]
+        });
+        node.render();
+
+        let names = backend
+            .mutations()
+            .iter()
+            .filter_map(|m| match m {
+                Mutation::SetAttribute { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["z", "class", "m"]);
+        assert_eq!(root.inner_html(), r#"<div z="1" class="a b" m="2"></div>"#);
+    }
+
+    #[test]
+    fn it_updates_class_and_style_by_entries() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let is_active = Rc::new(Cell::new(true));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let is_active = Rc::clone(&is_active);
+            move |_| {
+                let top = if is_active.get() { "0" } else { "1px" };
+                vec![Html::div(
+                    Attributes::new()
+                        .class("item")
+                        .class_if(is_active.get(), "active")
+                        .style("color", "red")
+                        .style("top", top),
+                    Events::new(),
+                    vec![],
+                )]
+            }
+        });
+        node.render();
+
+        let div = root.children()[0].clone();
+        assert_eq!(div.classes(), vec!["item", "active"]);
+
+        // set by others
+        div.set_attribute("class", "item active animating");
+        div.set_attribute("style", "color:red;top:0;opacity:0.5");
+        backend.clear_mutations();
+
+        is_active.set(false);
+        node.render();
+
+        assert_eq!(
+            backend.mutations(),
+            vec![
+                Mutation::RemoveClass {
+                    name: String::from("active")
+                },
+                Mutation::SetStyleProperty {
+                    name: String::from("top"),
+                    value: String::from("1px")
+                },
+            ]
+        );
+        assert_eq!(div.classes(), vec!["item", "animating"]);
+        assert_eq!(
+            div.attribute("style").as_deref(),
+            Some("color:red;top:1px;opacity:0.5")
+        );
+    }
+
+    #[test]
+    fn it_creates_svg_elements_in_svg_namespace() {
+        use crate::html::html_element::{PathData, Transform};
+        use crate::html::namespace;
+
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), |_| {
+            vec![Html::svg(
+                Attributes::new().view_box(0.0, 0.0, 20.0, 10.0),
+                Events::new(),
+                vec![Html::svg_path(
+                    Attributes::new()
+                        .d(PathData::new().move_to(0.0, 0.0).line_to(10.5, 0.0).close())
+                        .transform(Transform::new().translate(1.0, 2.0).rotate(45.0))
+                        .stroke_width(2.0),
+                    Events::new(),
+                    vec![],
+                )],
+            )]
+        });
+        node.render();
+
+        let svg = namespace::SVG.to_string();
+        assert_eq!(
+            backend.count_mutations(|m| matches!(
+                m,
+                Mutation::CreateElement { namespace: Some(ns), .. } if *ns == svg
+            )),
+            2
+        );
+        assert_eq!(
+            root.inner_html(),
+            r#"<svg viewBox="0 0 20 10"><path d="M0 0 L10.5 0 Z" transform="translate(1 2) rotate(45)" stroke-width="2"></path></svg>"#
+        );
+    }
+
+    #[test]
+    fn it_creates_mathml_elements_in_mathml_namespace() {
+        use crate::html::namespace;
+
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), |_| {
+            vec![Html::math(
+                Attributes::new(),
+                Events::new(),
+                vec![Html::mfrac(
+                    Attributes::new(),
+                    Events::new(),
+                    vec![
+                        Html::mn(Attributes::new(), Events::new(), vec![Html::text("1")]),
+                        Html::element(
+                            "mn",
+                            Attributes::new(),
+                            Events::new(),
+                            vec![Html::text("2")],
+                        ),
+                    ],
+                )],
+            )]
+        });
+        node.render();
+
+        let mathml = namespace::MATHML.to_string();
+        assert_eq!(
+            backend.count_mutations(|m| matches!(
+                m,
+                Mutation::CreateElement { namespace: Some(ns), .. } if *ns == mathml
+            )),
+            4
+        );
+        assert_eq!(
+            root.inner_html(),
+            "<math><mfrac><mn>1</mn><mn>2</mn></mfrac></math>"
+        );
+    }
+
+    #[test]
+    fn it_creates_children_of_svg_in_svg_namespace() {
+        use crate::html::namespace;
+
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), |_| {
+            vec![Html::svg(
+                Attributes::new(),
+                Events::new(),
+                vec![
+                    Html::element("circle", Attributes::new(), Events::new(), vec![]),
+                    Html::svg_foreign_object(
+                        Attributes::new(),
+                        Events::new(),
+                        vec![Html::div(Attributes::new(), Events::new(), vec![])],
+                    ),
+                ],
+            )]
+        });
+        node.render();
+
+        let created = backend
+            .mutations()
+            .into_iter()
+            .filter_map(|m| match m {
+                Mutation::CreateElement {
+                    tag_name,
+                    namespace,
+                } => Some((tag_name, namespace)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let svg = Some(namespace::SVG.to_string());
+        assert_eq!(
+            created,
+            vec![
+                (String::from("svg"), svg.clone()),
+                (String::from("circle"), svg.clone()),
+                (String::from("foreignObject"), svg),
+                (String::from("div"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_resolves_prefixed_namespaces() {
+        use crate::html::namespace;
+
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), |_| {
+            vec![
+                Html::svg(
+                    Attributes::new().string("xmlns:ex", "urn:example"),
+                    Events::new(),
+                    vec![
+                        Html::svg_use(
+                            Attributes::new().string("xlink:href", "#icon"),
+                            Events::new(),
+                            vec![],
+                        ),
+                        Html::element_ns("item", "ex", Attributes::new(), Events::new(), vec![]),
+                    ],
+                ),
+                Html::element_ns("item", "ex", Attributes::new(), Events::new(), vec![]),
+            ]
+        });
+        node.render();
+
+        let mutations = backend.mutations();
+        assert!(mutations.contains(&Mutation::SetAttributeNs {
+            namespace: String::from(namespace::XMLNS),
+            name: String::from("xmlns:ex"),
+            value: String::from("urn:example"),
+        }));
+        assert!(mutations.contains(&Mutation::SetAttributeNs {
+            namespace: String::from(namespace::XLINK),
+            name: String::from("xlink:href"),
+            value: String::from("#icon"),
+        }));
+
+        let namespaces = mutations
+            .iter()
+            .filter_map(|m| match m {
+                Mutation::CreateElement {
+                    tag_name,
+                    namespace,
+                } if tag_name == "item" => Some(namespace.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(namespaces, vec![Some(String::from("urn:example")), None]);
+    }
+
+    #[test]
+    fn it_adds_direct_listeners_to_elements() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let state = Rc::new(Cell::new((true, false)));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let state = Rc::clone(&state);
+            move |this| {
+                let (is_shown, is_passive) = state.get();
+                if !is_shown {
+                    return vec![];
+                }
+                let events = Events::new().on_scroll(this, |_| ());
+                let events = if is_passive {
+                    events.passive("scroll")
+                } else {
+                    events
+                };
+                vec![Html::div(Attributes::new(), events, vec![])]
+            }
+        });
+        node.render();
+
+        let div = root.children()[0].clone();
+        assert_eq!(backend.event_listeners().len(), 1);
+        assert!(backend.event_listeners()[0].target == div);
+        assert!(!backend.event_listeners()[0].options.capture);
+
+        backend.clear_mutations();
+        node.render();
+        assert_eq!(
+            backend.count_mutations(|m| matches!(
+                m,
+                Mutation::AddEventListener { .. } | Mutation::RemoveEventListener { .. }
+            )),
+            0
+        );
+
+        state.set((true, true));
+        node.render();
+        assert_eq!(backend.event_listeners().len(), 1);
+        assert!(backend.event_listeners()[0].options.passive);
+
+        state.set((false, true));
+        node.render();
+        assert_eq!(backend.event_listeners().len(), 0);
+    }
+
+    #[test]
+    fn it_removes_unused_root_listeners() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let is_clickable = Rc::new(Cell::new(true));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let is_clickable = Rc::clone(&is_clickable);
+            move |this| {
+                let events = Events::new().on_keydown(this, |_| ());
+                let events = if is_clickable.get() {
+                    events.on_click(this, |_| ())
+                } else {
+                    events
+                };
+                vec![Html::div(Attributes::new(), events, vec![])]
+            }
+        });
+        node.render();
+        assert_eq!(backend.event_listeners().len(), 2);
+        assert!(backend
+            .event_listeners()
+            .iter()
+            .all(|listener| listener.target == root && listener.options.capture));
+
+        is_clickable.set(false);
+        node.render();
+        assert_eq!(backend.event_listeners().len(), 1);
+        assert_eq!(backend.event_listeners()[0].event_type, "keydown");
+
+        drop(node);
+        assert_eq!(backend.event_listeners().len(), 0);
+    }
+
+    #[test]
+    fn it_dispatches_synthetic_events_to_handlers() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let calls = Rc::new(RefCell::new(vec![]));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let calls = Rc::clone(&calls);
+            move |this| {
+                let record = |name: &'static str| {
+                    let calls = Rc::clone(&calls);
+                    move |e: VEvent<web_sys::Event>| {
+                        calls.borrow_mut().push(name);
+                        if name == "button" {
+                            e.prevent_default();
+                        }
+                    }
+                };
+                vec![Html::div(
+                    Attributes::new(),
+                    Events::new().on("click", this, record("div")),
+                    vec![Html::button(
+                        Attributes::new(),
+                        Events::new()
+                            .on("click", this, record("button"))
+                            .on("focus", this, record("focus"))
+                            .direct("focus", VListenerOptions::default()),
+                        vec![],
+                    )],
+                )]
+            }
+        });
+        node.render();
+        let button = root.children()[0].children()[0].clone();
+
+        assert!(!backend.dispatch_event(&button, "click", true));
+        assert_eq!(*calls.borrow(), vec!["button", "div"]);
+
+        calls.borrow_mut().clear();
+        assert!(backend.dispatch_event(&button, "focus", false));
+        assert_eq!(*calls.borrow(), vec!["focus"]);
+    }
+
+    #[test]
+    fn it_reads_typed_events_through_backend() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let calls = Rc::new(RefCell::new(vec![]));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let calls = Rc::clone(&calls);
+            move |this| {
+                let on_keydown = {
+                    let calls = Rc::clone(&calls);
+                    move |e: VEvent<web_sys::KeyboardEvent>| {
+                        let tag_name = e.event_target().and_then(|target| target.tag_name());
+                        calls
+                            .borrow_mut()
+                            .push(format!("{:?}:{}", tag_name, e.key()));
+                        e.stop_native_propagation();
+                    }
+                };
+                let on_click = {
+                    let calls = Rc::clone(&calls);
+                    move |e: VEvent<web_sys::MouseEvent>| {
+                        calls.borrow_mut().push(String::from("click"));
+                        e.prevent_default();
+                    }
+                };
+                vec![Html::input(
+                    Attributes::new(),
+                    Events::new()
+                        .on_keydown(this, on_keydown)
+                        .on_click(this, on_click),
+                    vec![],
+                )]
+            }
+        });
+        node.render();
+        let input = root.children()[0].clone();
+        let is_reached = Rc::new(Cell::new(false));
+        let parent = backend.create_root();
+        backend.insert_before(&parent, &root, None);
+        backend.add_event_listener(
+            &parent,
+            "keydown",
+            Box::new({
+                let is_reached = Rc::clone(&is_reached);
+                move |_| is_reached.set(true)
+            }),
+            DomEventListenerOptions::default(),
+        );
+
+        assert!(backend.dispatch_key_event(&input, "keydown", "Enter"));
+        assert!(!backend.dispatch_event(&input, "click", true));
+        assert_eq!(
+            *calls.borrow(),
+            vec![String::from("Some(\"input\"):Enter"), String::from("click")]
+        );
+        assert!(!is_reached.get());
+    }
+
+    #[test]
+    fn it_dispatches_nested_events_re_entrantly() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let inner = Rc::new(RefCell::new(None));
+        let is_nested_not_canceled = Rc::new(Cell::new(None));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let backend = backend.clone();
+            let inner = Rc::clone(&inner);
+            let is_nested_not_canceled = Rc::clone(&is_nested_not_canceled);
+            move |this| {
+                let on_outer_click = {
+                    let backend = backend.clone();
+                    let inner = Rc::clone(&inner);
+                    let is_nested_not_canceled = Rc::clone(&is_nested_not_canceled);
+                    move |_: VEvent<web_sys::Event>| {
+                        if let Some(inner) = inner.borrow().as_ref() {
+                            is_nested_not_canceled
+                                .set(Some(backend.dispatch_event(inner, "click", true)));
+                        }
+                    }
+                };
+                vec![
+                    Html::button(
+                        Attributes::new(),
+                        Events::new().on("click", this, on_outer_click),
+                        vec![],
+                    ),
+                    Html::button(
+                        Attributes::new(),
+                        Events::new().on("click", this, |e: VEvent<web_sys::Event>| {
+                            e.prevent_default()
+                        }),
+                        vec![],
+                    ),
+                ]
+            }
+        });
+        node.render();
+        let outer = root.children()[0].clone();
+        *inner.borrow_mut() = Some(root.children()[1].clone());
+
+        assert!(backend.dispatch_event(&outer, "click", true));
+        assert_eq!(is_nested_not_canceled.get(), Some(false));
+    }
+
+    #[test]
+    fn it_writes_value_only_if_live_value_differs() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), |_| {
+            vec![Html::input(
+                Attributes::new().value("a"),
+                Events::new(),
+                vec![],
+            )]
+        });
+        node.render();
+        let input = root.children()[0].clone();
+        assert_eq!(input.value().as_deref(), Some("a"));
+
+        backend.clear_mutations();
+        node.render();
+        assert_eq!(
+            backend.count_mutations(|m| matches!(m, Mutation::SetValue { .. })),
+            0
+        );
+
+        input.set_value("ab");
+        node.render();
+        assert_eq!(input.value().as_deref(), Some("a"));
+        assert_eq!(
+            backend.count_mutations(|m| matches!(m, Mutation::SetValue { .. })),
+            1
+        );
+    }
+
+    #[test]
+    fn it_writes_deferred_value_when_composition_ends() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let value = Rc::new(RefCell::new(String::from("a")));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let value = Rc::clone(&value);
+            move |_| {
+                vec![Html::input(
+                    Attributes::new().value(value.borrow().as_str()),
+                    Events::new(),
+                    vec![],
+                )]
+            }
+        });
+        node.render();
+        let input = root.children()[0].clone();
+
+        backend.dispatch_event(&input, "compositionstart", true);
+        input.set_value("a\u{304b}");
+        *value.borrow_mut() = String::from("ab");
+        backend.clear_mutations();
+        node.render();
+        assert_eq!(input.value().as_deref(), Some("a\u{304b}"));
+        assert_eq!(
+            backend.count_mutations(|m| matches!(m, Mutation::SetValue { .. })),
+            0
+        );
+
+        backend.dispatch_event(&input, "compositionend", true);
+        assert_eq!(input.value().as_deref(), Some("ab"));
+        assert_eq!(
+            backend.count_mutations(|m| matches!(m, Mutation::SetValue { .. })),
+            1
+        );
+    }
+
+    #[test]
+    fn it_writes_property_only_if_live_property_differs() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let checked = Rc::new(Cell::new(Some(true)));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let checked = Rc::clone(&checked);
+            move |_| {
+                let attrs = Attributes::new().type_("checkbox");
+                let attrs = match checked.get() {
+                    Some(true) => attrs.property("checked", JsValue::TRUE),
+                    Some(false) => attrs.property("checked", JsValue::FALSE),
+                    None => attrs,
+                };
+                vec![Html::input(attrs, Events::new(), vec![])]
+            }
+        });
+        let count_set_property =
+            || backend.count_mutations(|m| matches!(m, Mutation::SetProperty { .. }));
+        node.render();
+        let input = root.children()[0].clone();
+        assert!(backend.is_property(&input, "checked", &JsValue::TRUE));
+        assert!(input.attribute("checked").is_none());
+
+        backend.clear_mutations();
+        node.render();
+        assert_eq!(count_set_property(), 0);
+
+        checked.set(Some(false));
+        node.render();
+        assert_eq!(count_set_property(), 1);
+        assert!(backend.is_property(&input, "checked", &JsValue::FALSE));
+
+        backend.clear_mutations();
+        checked.set(None);
+        node.render();
+        assert_eq!(count_set_property(), 1);
+        assert!(backend.is_property(&input, "checked", &JsValue::UNDEFINED));
+    }
+
+    #[test]
+    fn it_restores_removed_property_to_live_value_before_first_write() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let has_title = Rc::new(Cell::new(false));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let has_title = Rc::clone(&has_title);
+            move |_| {
+                let attrs = Attributes::new();
+                let attrs = match has_title.get() {
+                    // strings can not be made natively, so constants stand for them.
+                    true => attrs.property("title", JsValue::TRUE),
+                    false => attrs,
+                };
+                vec![Html::div(attrs, Events::new(), vec![])]
+            }
+        });
+        node.render();
+        let div = root.children()[0].clone();
+        // a default value of the element, like `title = ""`.
+        let default = Rc::new(JsValue::NULL);
+        backend.set_property(&div, "title", Rc::clone(&default));
+
+        has_title.set(true);
+        node.render();
+        assert!(backend.is_property(&div, "title", &JsValue::TRUE));
+        node.render();
+
+        has_title.set(false);
+        node.render();
+        assert!(backend.is_property(&div, "title", &default));
+        assert!(!backend.is_property(&div, "title", &JsValue::UNDEFINED));
+    }
+
+    #[test]
+    fn it_attaches_and_detaches_node_refs() {
+        use crate::NodeRef;
+
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let outer = NodeRef::<web_sys::Element>::new();
+        let inner = NodeRef::<web_sys::Element>::new();
+        let step = Rc::new(Cell::new(0));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let outer = outer.clone();
+            let inner = inner.clone();
+            let step = Rc::clone(&step);
+            move |_| {
+                let child = Html::p(Attributes::new(), Events::new().node_ref(&inner), vec![]);
+                match step.get() {
+                    0 => vec![Html::div(
+                        Attributes::new(),
+                        Events::new().node_ref(&outer),
+                        vec![child],
+                    )],
+                    1 => vec![Html::span(
+                        Attributes::new(),
+                        Events::new().node_ref(&outer),
+                        vec![child],
+                    )],
+                    _ => vec![],
+                }
+            }
+        });
+        let raw = |node_ref: &NodeRef<web_sys::Element>| node_ref.raw::<MemoryDomBackend>();
+
+        node.render();
+        let div = root.children()[0].clone();
+        let p = div.children()[0].clone();
+        assert!(raw(&outer) == Some(div));
+        assert!(raw(&inner) == Some(p.clone()));
+
+        step.set(1);
+        node.render();
+        let span = root.children()[0].clone();
+        assert_eq!(span.tag_name().as_deref(), Some("span"));
+        assert!(raw(&outer) == Some(span.clone()));
+        assert!(raw(&inner) == Some(span.children()[0].clone()));
+        assert!(raw(&inner) != Some(p));
+
+        step.set(2);
+        node.render();
+        assert!(!outer.is_attached());
+        assert!(!inner.is_attached());
+    }
+
+    #[test]
+    fn it_renders_portal_into_target() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let target = backend.create_root();
+        let text = Rc::new(Cell::new("a"));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let target = target.clone();
+            let text = Rc::clone(&text);
+            move |_| {
+                vec![Html::portal_to(
+                    PortalTarget::new(target.clone()),
+                    vec![Html::p(
+                        Attributes::new(),
+                        Events::new(),
+                        vec![Html::text(text.get())],
+                    )],
+                )]
+            }
+        });
+        node.render();
+
+        assert_eq!(root.inner_html(), "");
+        let container = target.children()[0].clone();
+        assert_eq!(container.tag_name().as_deref(), Some("div"));
+        assert_eq!(container.inner_html(), "<p>a</p>");
+        let p = container.children()[0].clone();
+
+        backend.clear_mutations();
+        text.set("b");
+        node.render();
+
+        assert_eq!(target.children().len(), 1);
+        assert!(target.children()[0] == container);
+        assert!(container.children()[0] == p);
+        assert_eq!(container.inner_html(), "<p>b</p>");
+        assert_eq!(
+            backend.count_mutations(|m| matches!(
+                m,
+                Mutation::CreateElement { .. }
+                    | Mutation::CreateTextNode { .. }
+                    | Mutation::InsertBefore
+                    | Mutation::ReplaceChild
+                    | Mutation::RemoveChild
+            )),
+            0
+        );
+    }
+
+    #[test]
+    fn it_removes_portal_containers() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let target = backend.create_root();
+        let step = Rc::new(Cell::new(0));
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let target = target.clone();
+            let step = Rc::clone(&step);
+            move |_| {
+                let portal = |text: &str| {
+                    Html::portal_to(PortalTarget::new(target.clone()), vec![Html::text(text)])
+                };
+                let nested = Html::div(
+                    Attributes::new(),
+                    Events::new(),
+                    vec![Html::section(
+                        Attributes::new(),
+                        Events::new(),
+                        vec![portal("nested")],
+                    )],
+                );
+                match step.get() {
+                    0 => vec![portal("direct"), nested],
+                    1 => vec![nested],
+                    _ => vec![],
+                }
+            }
+        });
+        node.render();
+        assert_eq!(target.children().len(), 2);
+
+        step.set(1);
+        node.render();
+        assert_eq!(target.children().len(), 1);
+        assert_eq!(target.children()[0].inner_html(), "nested");
+
+        step.set(2);
+        node.render();
+        assert_eq!(target.children().len(), 0);
+    }
+}
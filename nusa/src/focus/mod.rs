@@ -0,0 +1,158 @@
+//! Focus management of a subtree.
+//!
+//! ```ignore
+//! // in render
+//! FocusTrap::new(
+//!     self,
+//!     None,
+//!     focus::Props {},
+//!     Sub::none(),
+//!     (
+//!         Attributes::new().role(AriaRole::Dialog).aria_modal(true),
+//!         vec![
+//!             Html::input(Attributes::new().autofocus(true), Events::new(), vec![]),
+//!             Html::button(Attributes::new(), Events::new().on_click(self, |_| Msg::Close), vec![Html::text("close")]),
+//!         ],
+//!     ),
+//! )
+//! ```
+
+use crate::dom_ref::NodeRef;
+use crate::html::html_element::{Attributes, Events};
+use crate::{Html, HtmlComponent};
+use kagura::component::{Cmd, Constructor, Render, Update};
+use kagura::Component;
+use std::pin::Pin;
+use wasm_bindgen::JsCast;
+
+const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), input:not([disabled]):not([type=hidden]), select:not([disabled]), textarea:not([disabled]), iframe, [contenteditable], [tabindex]:not([tabindex='-1'])";
+
+pub struct Props {}
+
+pub enum Msg {
+    Attach(web_sys::HtmlElement),
+    Ignore,
+}
+
+pub enum On {}
+
+/// Confines `Tab` navigation in children, and restores the previous focus on unmount.
+/// It focuses the first focusable child on mount unless focus is already in it, e.g. by `autofocus`.
+pub struct FocusTrap {
+    container: NodeRef<web_sys::HtmlElement>,
+    previous_focus: Option<web_sys::HtmlElement>,
+}
+
+impl FocusTrap {
+    fn focusable_elements(container: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+        let mut elements = vec![];
+        if let Ok(nodes) = container.query_selector_all(FOCUSABLE) {
+            for i in 0..nodes.length() {
+                if let Some(element) = nodes.get(i).and_then(|node| node.dyn_into().ok()) {
+                    elements.push(element);
+                }
+            }
+        }
+        elements
+    }
+
+    /// Moves focus to the other edge if it is on an edge or out of `container`.
+    /// Returns `true` if the default move should be prevented.
+    fn cycle(container: &web_sys::Element, is_backward: bool) -> bool {
+        let elements = Self::focusable_elements(container);
+        let (edge, next) = if is_backward {
+            (elements.first(), elements.last())
+        } else {
+            (elements.last(), elements.first())
+        };
+        let Some(next) = next else {
+            return true;
+        };
+
+        let active = active_element();
+        let is_inside = active.as_ref().is_some_and(|active| {
+            container.contains(Some(active)) && !container.is_same_node(Some(active))
+        });
+        let is_on_edge = active
+            .as_ref()
+            .is_some_and(|active| edge.is_some_and(|edge| edge.is_same_node(Some(active))));
+
+        if is_on_edge || !is_inside {
+            let _ = next.focus();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn active_element() -> Option<web_sys::Element> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.active_element())
+}
+
+impl Component for FocusTrap {
+    type Props = Props;
+    type Msg = Msg;
+    type Event = On;
+}
+
+impl Constructor for FocusTrap {
+    fn constructor(_props: Self::Props) -> Self {
+        Self {
+            container: NodeRef::new(),
+            previous_focus: active_element().and_then(|element| element.dyn_into().ok()),
+        }
+    }
+}
+
+impl Update for FocusTrap {
+    fn update(self: Pin<&mut Self>, msg: Self::Msg) -> Cmd<Self> {
+        match msg {
+            Msg::Attach(container) => {
+                let is_inside =
+                    active_element().is_some_and(|active| container.contains(Some(&active)));
+                if !is_inside {
+                    let _ = match Self::focusable_elements(&container).first() {
+                        Some(first) => first.focus(),
+                        None => container.focus(),
+                    };
+                }
+                Cmd::none()
+            }
+            Msg::Ignore => Cmd::none(),
+        }
+    }
+}
+
+impl Render<Html> for FocusTrap {
+    type Children = (Attributes, Vec<Html>);
+    fn render(&self, (attrs, children): Self::Children) -> Html {
+        let container = self.container.clone();
+        Html::div(
+            attrs.tabindex(-1),
+            Events::new()
+                .node_ref_with(self, &self.container, Msg::Attach, || Msg::Ignore)
+                .on_keydown(self, move |e| {
+                    if let Some(container) = container.get().filter(|_| e.key() == "Tab") {
+                        if Self::cycle(&container, e.shift_key()) {
+                            e.prevent_default();
+                        }
+                    }
+                    Msg::Ignore
+                }),
+            children,
+        )
+    }
+}
+
+impl HtmlComponent for FocusTrap {}
+
+impl std::ops::Drop for FocusTrap {
+    fn drop(&mut self) {
+        if let Some(previous_focus) = &self.previous_focus {
+            let _ = previous_focus.focus();
+        }
+    }
+}
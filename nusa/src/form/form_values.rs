@@ -0,0 +1,158 @@
+use wasm_bindgen::JsCast;
+
+/// Builds a model from values of a form.
+pub trait FromForm: Sized {
+    fn from_form(values: &FormValues) -> Result<Self, FormErrors>;
+}
+
+/// Values of fields by name. A name can have multiple values like `FormData`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct FormValues {
+    values: Vec<(String, String)>,
+}
+
+/// Error messages by field name.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct FormErrors {
+    errors: Vec<(String, String)>,
+}
+
+impl FormValues {
+    pub fn new() -> Self {
+        Self { values: vec![] }
+    }
+
+    /// Values of `FormData`. Files are ignored.
+    pub fn from_form_data(form_data: &web_sys::FormData) -> Self {
+        let mut values = Self::new();
+        if let Ok(Some(entries)) = js_sys::try_iter(form_data) {
+            for entry in entries.flatten() {
+                let entry = entry.unchecked_into::<js_sys::Array>();
+                if let (Some(name), Some(value)) =
+                    (entry.get(0).as_string(), entry.get(1).as_string())
+                {
+                    values.append(name, value);
+                }
+            }
+        }
+        values
+    }
+
+    /// Values of controls in a `form` element.
+    pub fn from_form_element(form: &web_sys::HtmlFormElement) -> Self {
+        web_sys::FormData::new_with_form(form)
+            .map(|form_data| Self::from_form_data(&form_data))
+            .unwrap_or_default()
+    }
+
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.push((name.into(), value.into()));
+    }
+
+    /// Replaces all values of `name`.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        self.values.retain(|(n, _)| *n != name);
+        self.values.push((name, value.into()));
+    }
+
+    /// The first value of `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.values
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The first value of `name`, which is not empty.
+    pub fn required(&self, name: &str) -> Result<&str, FormErrors> {
+        match self.get(name) {
+            Some(value) if !value.trim().is_empty() => Ok(value),
+            _ => Err(FormErrors::single(name, "required")),
+        }
+    }
+
+    /// Parses the first value of `name`. Surrounding whitespaces are ignored.
+    pub fn parse<T: std::str::FromStr>(&self, name: &str) -> Result<T, FormErrors> {
+        self.required(name)?
+            .trim()
+            .parse()
+            .map_err(|_| FormErrors::single(name, "invalid value"))
+    }
+}
+
+impl FormErrors {
+    pub fn new() -> Self {
+        Self { errors: vec![] }
+    }
+
+    pub fn single(name: impl Into<String>, message: impl Into<String>) -> Self {
+        let mut errors = Self::new();
+        errors.push(name, message);
+        errors
+    }
+
+    pub fn push(&mut self, name: impl Into<String>, message: impl Into<String>) {
+        self.errors.push((name.into(), message.into()));
+    }
+
+    pub fn append(&mut self, other: Self) {
+        self.errors.extend(other.errors);
+    }
+
+    /// Messages of `name`.
+    pub fn get(&self, name: &str) -> Vec<&str> {
+        self.errors
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, message)| message.as_str())
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.errors
+            .iter()
+            .map(|(name, message)| (name.as_str(), message.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl std::fmt::Display for FormErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let errors = self
+            .errors
+            .iter()
+            .map(|(name, message)| format!("{}: {}", name, message))
+            .collect::<Vec<_>>();
+        write!(f, "{}", errors.join(", "))
+    }
+}
+
+impl std::error::Error for FormErrors {}
+
+impl FromForm for FormValues {
+    fn from_form(values: &FormValues) -> Result<Self, FormErrors> {
+        Ok(values.clone())
+    }
+}
@@ -0,0 +1,654 @@
+//! Forms with validation and submission.
+//!
+//! ```ignore
+//! struct Login {
+//!     email: String,
+//!     age: u32,
+//! }
+//!
+//! impl FromForm for Login {
+//!     fn from_form(values: &FormValues) -> Result<Self, FormErrors> {
+//!         Ok(Self {
+//!             email: values.required("email")?.to_string(),
+//!             age: values.parse("age")?,
+//!         })
+//!     }
+//! }
+//!
+//! // in constructor
+//! let form = Form::<Login>::new()
+//!     .field("email", "")
+//!     .validate("email", |v| if v.contains('@') { Ok(()) } else { Err("invalid email".into()) })
+//!     .field("age", "");
+//!
+//! // in update
+//! match msg {
+//!     Msg::Form(msg) => match self.form.update(msg, Msg::Form) {
+//!         FormCmd::Cmd(cmd) => cmd,
+//!         FormCmd::Submitted(Ok(login)) => Cmd::submit(On::Login(login)),
+//!         FormCmd::Submitted(Err(..)) => Cmd::none(),
+//!     },
+//! }
+//!
+//! // in render
+//! Html::form(
+//!     Attributes::new(),
+//!     self.form.events(self, Msg::Form),
+//!     vec![Html::input(
+//!         self.form.bind("email").attributes(),
+//!         self.form.bind("email").events(self, Msg::Form),
+//!         vec![],
+//!     )],
+//! )
+//! ```
+
+use crate::html::html_element::{Attributes, Events};
+use kagura::component::Cmd;
+use kagura::Component;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+
+pub mod form_values;
+
+pub use form_values::{FormErrors, FormValues, FromForm};
+
+pub type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+pub type AsyncValidator = Rc<dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<(), String>>>>>;
+
+pub enum FormMsg {
+    Input {
+        name: String,
+        value: String,
+    },
+    Blur {
+        name: String,
+    },
+    /// A result of async validators. It is ignored if the value is changed after validation started.
+    Validated {
+        name: String,
+        version: usize,
+        errors: Vec<String>,
+    },
+    /// Touches all fields, validates them and starts async validators which have not run
+    /// for the current values. `Form::update` returns `FormCmd::Submitted` when they are done.
+    Submit,
+    Reset,
+}
+
+/// A result of `Form::update`.
+pub enum FormCmd<T, C: Component> {
+    Cmd(Cmd<C>),
+    /// A submit is done after async validators of all fields.
+    Submitted(Result<T, FormErrors>),
+}
+
+pub struct Form<T: FromForm> {
+    fields: Vec<Field>,
+    submit_count: usize,
+    /// A submit waits for async validators.
+    is_submitting: bool,
+    _model: PhantomData<T>,
+}
+
+struct Field {
+    name: String,
+    initial: String,
+    value: String,
+    is_touched: bool,
+    errors: Vec<String>,
+    async_errors: Vec<String>,
+    validators: Vec<Validator>,
+    async_validators: Vec<AsyncValidator>,
+    version: usize,
+    is_validating: bool,
+    /// `version` of which async validators are done.
+    validated_version: Option<usize>,
+}
+
+pub struct FieldBinding<'a> {
+    name: String,
+    field: Option<&'a Field>,
+}
+
+impl<T: FromForm> Form<T> {
+    pub fn new() -> Self {
+        Self {
+            fields: vec![],
+            submit_count: 0,
+            is_submitting: false,
+            _model: PhantomData,
+        }
+    }
+
+    pub fn field(mut self, name: impl Into<String>, initial: impl Into<String>) -> Self {
+        let initial = initial.into();
+        self.fields.push(Field {
+            name: name.into(),
+            value: initial.clone(),
+            initial,
+            is_touched: false,
+            errors: vec![],
+            async_errors: vec![],
+            validators: vec![],
+            async_validators: vec![],
+            version: 0,
+            is_validating: false,
+            validated_version: None,
+        });
+        self
+    }
+
+    /// Adds a validator which runs on every input.
+    pub fn validate(
+        mut self,
+        name: &str,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        if let Some(field) = self.field_mut(name) {
+            field.validators.push(Box::new(validator));
+        }
+        self
+    }
+
+    /// Adds a validator which runs as `Cmd::Task` after synchronous validators pass.
+    pub fn validate_async<F: Future<Output = Result<(), String>> + 'static>(
+        mut self,
+        name: &str,
+        validator: impl Fn(String) -> F + 'static,
+    ) -> Self {
+        if let Some(field) = self.field_mut(name) {
+            field
+                .async_validators
+                .push(Rc::new(move |value| Box::pin(validator(value))));
+        }
+        self
+    }
+
+    /// Handles `FormMsg`. `map` wraps `FormMsg` of async validators into `C::Msg`.
+    /// An input cancels a submit which waits for async validators.
+    pub fn update<C: Component + 'static>(
+        &mut self,
+        msg: FormMsg,
+        map: impl Fn(FormMsg) -> C::Msg + 'static,
+    ) -> FormCmd<T, C> {
+        match msg {
+            FormMsg::Input { name, value } => {
+                self.is_submitting = false;
+                if let Some(field) = self.field_mut(&name) {
+                    field.value = value;
+                    field.validate();
+                    return FormCmd::Cmd(field.validate_async(map));
+                }
+                FormCmd::Cmd(Cmd::none())
+            }
+            FormMsg::Blur { name } => {
+                if let Some(field) = self.field_mut(&name) {
+                    field.is_touched = true;
+                }
+                FormCmd::Cmd(Cmd::none())
+            }
+            FormMsg::Validated {
+                name,
+                version,
+                errors,
+            } => {
+                if let Some(field) = self.field_mut(&name) {
+                    if field.version == version {
+                        field.async_errors = errors;
+                        field.is_validating = false;
+                        field.validated_version = Some(version);
+                    }
+                }
+                self.complete_submit()
+            }
+            FormMsg::Submit => {
+                self.touch_all();
+                self.submit_count += 1;
+                self.is_submitting = true;
+                let map = Rc::new(map);
+                let cmds = self
+                    .fields
+                    .iter_mut()
+                    .filter(|field| field.needs_async_validation())
+                    .map(|field| {
+                        let map = Rc::clone(&map);
+                        field.validate_async(move |msg| map(msg))
+                    })
+                    .collect::<Vec<_>>();
+                if cmds.is_empty() {
+                    self.complete_submit()
+                } else {
+                    FormCmd::Cmd(Cmd::list(cmds))
+                }
+            }
+            FormMsg::Reset => {
+                self.reset();
+                FormCmd::Cmd(Cmd::none())
+            }
+        }
+    }
+
+    /// Validates all fields and builds the model without async validators.
+    /// Fields whose async validators are running, or have not run for the current value, are errors.
+    pub fn submit(&mut self) -> Result<T, FormErrors> {
+        self.touch_all();
+        self.submit_count += 1;
+        self.build()
+    }
+
+    pub fn reset(&mut self) {
+        self.is_submitting = false;
+        for field in &mut self.fields {
+            field.value = field.initial.clone();
+            field.is_touched = false;
+            field.errors.clear();
+            field.async_errors.clear();
+            field.version += 1;
+            field.is_validating = false;
+            field.validated_version = None;
+        }
+    }
+
+    pub fn values(&self) -> FormValues {
+        let mut values = FormValues::new();
+        for field in &self.fields {
+            values.append(field.name.as_str(), field.value.as_str());
+        }
+        values
+    }
+
+    /// Errors of synchronous and async validators.
+    pub fn errors(&self) -> FormErrors {
+        let mut errors = FormErrors::new();
+        for field in &self.fields {
+            for error in field.errors.iter().chain(field.async_errors.iter()) {
+                errors.push(field.name.as_str(), error.as_str());
+            }
+        }
+        errors
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.fields.iter().all(|field| field.is_valid())
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.fields.iter().any(|field| field.is_dirty())
+    }
+
+    pub fn is_validating(&self) -> bool {
+        self.fields.iter().any(|field| field.is_validating)
+    }
+
+    /// A submit waits for async validators.
+    pub fn is_submitting(&self) -> bool {
+        self.is_submitting
+    }
+
+    pub fn submit_count(&self) -> usize {
+        self.submit_count
+    }
+
+    pub fn bind(&self, name: impl Into<String>) -> FieldBinding<'_> {
+        let name = name.into();
+        let field = self.fields.iter().find(|field| field.name == name);
+        FieldBinding { name, field }
+    }
+
+    /// Events of the `form` element. `submit` is handled with `preventDefault`.
+    pub fn events<C: Component + 'static>(
+        &self,
+        target: &C,
+        map: impl Fn(FormMsg) -> C::Msg + 'static,
+    ) -> Events {
+        Events::new().on_submit(target, move |e| {
+            e.prevent_default();
+            map(FormMsg::Submit)
+        })
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut Field> {
+        self.fields.iter_mut().find(|field| field.name == name)
+    }
+
+    fn build(&self) -> Result<T, FormErrors> {
+        let mut errors = self.errors();
+        for field in &self.fields {
+            if field.is_validating {
+                errors.push(field.name.as_str(), "validating");
+            } else if field.needs_async_validation() {
+                errors.push(field.name.as_str(), "not validated");
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        T::from_form(&self.values())
+    }
+
+    fn complete_submit<C: Component>(&mut self) -> FormCmd<T, C> {
+        if !self.is_submitting || self.is_validating() {
+            return FormCmd::Cmd(Cmd::none());
+        }
+        self.is_submitting = false;
+        FormCmd::Submitted(self.build())
+    }
+
+    fn touch_all(&mut self) {
+        for field in &mut self.fields {
+            field.is_touched = true;
+            field.validate();
+        }
+    }
+}
+
+impl<T: FromForm> std::default::Default for Form<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Field {
+    fn validate(&mut self) {
+        self.errors = self
+            .validators
+            .iter()
+            .filter_map(|validator| validator(&self.value).err())
+            .collect();
+    }
+
+    fn validate_async<C: Component + 'static>(
+        &mut self,
+        map: impl Fn(FormMsg) -> C::Msg + 'static,
+    ) -> Cmd<C> {
+        self.version += 1;
+        self.async_errors.clear();
+
+        if !self.errors.is_empty() || self.async_validators.is_empty() {
+            self.is_validating = false;
+            return Cmd::none();
+        }
+
+        self.is_validating = true;
+        let name = self.name.clone();
+        let version = self.version;
+        let value = self.value.clone();
+        let validators = self.async_validators.clone();
+
+        Cmd::task(async move {
+            let mut errors = vec![];
+            for validator in validators {
+                if let Err(error) = validator(value.clone()).await {
+                    errors.push(error);
+                }
+            }
+            Cmd::chain(map(FormMsg::Validated {
+                name,
+                version,
+                errors,
+            }))
+        })
+    }
+
+    fn is_valid(&self) -> bool {
+        self.errors.is_empty() && self.async_errors.is_empty()
+    }
+
+    /// Async validators run only if synchronous validators pass.
+    fn needs_async_validation(&self) -> bool {
+        !self.async_validators.is_empty()
+            && self.errors.is_empty()
+            && !self.is_validating
+            && self.validated_version != Some(self.version)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.value != self.initial
+    }
+}
+
+impl<'a> FieldBinding<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &str {
+        self.field.map(|field| field.value.as_str()).unwrap_or("")
+    }
+
+    pub fn errors(&self) -> Vec<&'a str> {
+        self.field
+            .map(|field| {
+                field
+                    .errors
+                    .iter()
+                    .chain(field.async_errors.iter())
+                    .map(|error| error.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Errors after the field is touched.
+    pub fn visible_errors(&self) -> Vec<&'a str> {
+        if self.is_touched() {
+            self.errors()
+        } else {
+            vec![]
+        }
+    }
+
+    pub fn is_touched(&self) -> bool {
+        self.field.map(|field| field.is_touched).unwrap_or(false)
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.field.map(|field| field.is_dirty()).unwrap_or(false)
+    }
+
+    pub fn is_validating(&self) -> bool {
+        self.field.map(|field| field.is_validating).unwrap_or(false)
+    }
+
+    /// `name` and `value` of the control.
+    pub fn attributes(&self) -> Attributes {
+        Attributes::new()
+            .string("name", self.name.as_str())
+            .value(self.value())
+    }
+
+    /// `input` and `blur` of the control.
+    pub fn events<C: Component + 'static>(
+        &self,
+        target: &C,
+        map: impl Fn(FormMsg) -> C::Msg + 'static,
+    ) -> Events {
+        let map = Rc::new(map);
+        let input_name = self.name.clone();
+        let blur_name = self.name.clone();
+        Events::new()
+            .on_input(target, {
+                let map = Rc::clone(&map);
+                move |value| {
+                    map(FormMsg::Input {
+                        name: input_name.clone(),
+                        value,
+                    })
+                }
+            })
+            .on_blur(target, move |_| {
+                map(FormMsg::Blur {
+                    name: blur_name.clone(),
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom_node::basic_dom_node::BasicDomComponent;
+
+    #[derive(Debug, PartialEq)]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    impl FromForm for Person {
+        fn from_form(values: &FormValues) -> Result<Self, FormErrors> {
+            Ok(Self {
+                name: values.required("name")?.to_string(),
+                age: values.parse("age")?,
+            })
+        }
+    }
+
+    fn input(form: &mut Form<Person>, name: &str, value: &str) {
+        let _ = form.update::<BasicDomComponent>(
+            FormMsg::Input {
+                name: String::from(name),
+                value: String::from(value),
+            },
+            |_| (),
+        );
+    }
+
+    fn person_form() -> Form<Person> {
+        Form::new()
+            .field("name", "")
+            .validate("name", |name| {
+                if name.len() <= 8 {
+                    Ok(())
+                } else {
+                    Err(String::from("too long"))
+                }
+            })
+            .field("age", "")
+    }
+
+    #[test]
+    fn it_validates_and_submits() {
+        let mut form = person_form();
+        assert!(!form.bind("name").is_touched());
+
+        input(&mut form, "name", "Alexander the Great");
+        assert!(form.is_dirty());
+        assert_eq!(form.bind("name").errors(), vec!["too long"]);
+        assert!(form.bind("name").visible_errors().is_empty());
+
+        let errors = form.submit().unwrap_err();
+        assert_eq!(errors.get("name"), vec!["too long"]);
+        assert!(form.bind("name").is_touched());
+
+        input(&mut form, "name", "Alex");
+        input(&mut form, "age", "x");
+        assert_eq!(form.submit().unwrap_err().get("age"), vec!["invalid value"]);
+
+        input(&mut form, "age", " 20 ");
+        assert_eq!(
+            form.submit(),
+            Ok(Person {
+                name: String::from("Alex"),
+                age: 20
+            })
+        );
+    }
+
+    #[test]
+    fn it_ignores_stale_async_results() {
+        let mut form = person_form().validate_async("name", |_| async { Ok(()) });
+
+        input(&mut form, "name", "Alex");
+        input(&mut form, "name", "Bob");
+        assert!(form.is_validating());
+
+        let _ = form.update::<BasicDomComponent>(
+            FormMsg::Validated {
+                name: String::from("name"),
+                version: 1,
+                errors: vec![String::from("taken")],
+            },
+            |_| (),
+        );
+        assert!(form.is_validating());
+        assert!(form.bind("name").errors().is_empty());
+
+        let _ = form.update::<BasicDomComponent>(
+            FormMsg::Validated {
+                name: String::from("name"),
+                version: 2,
+                errors: vec![String::from("taken")],
+            },
+            |_| (),
+        );
+        assert!(!form.is_validating());
+        assert_eq!(form.bind("name").errors(), vec!["taken"]);
+    }
+
+    fn validated(form: &mut Form<Person>, version: usize) -> FormCmd<Person, BasicDomComponent> {
+        form.update(
+            FormMsg::Validated {
+                name: String::from("name"),
+                version,
+                errors: vec![],
+            },
+            |_| (),
+        )
+    }
+
+    #[test]
+    fn it_submits_after_async_validators() {
+        let mut form = Form::<Person>::new()
+            .field("name", "Alex")
+            .validate_async("name", |_| async { Ok(()) })
+            .field("age", "20");
+
+        assert!(matches!(
+            form.update::<BasicDomComponent>(FormMsg::Submit, |_| ()),
+            FormCmd::Cmd(..)
+        ));
+        assert!(form.is_submitting());
+        assert!(form.bind("name").is_validating());
+        assert_eq!(form.submit_count(), 1);
+
+        match validated(&mut form, 1) {
+            FormCmd::Submitted(person) => assert_eq!(
+                person,
+                Ok(Person {
+                    name: String::from("Alex"),
+                    age: 20
+                })
+            ),
+            FormCmd::Cmd(..) => panic!("submit is not done"),
+        }
+        assert!(!form.is_submitting());
+        assert_eq!(form.submit_count(), 1);
+
+        // the result is cached, so the next submit is done immediately.
+        assert!(matches!(
+            form.update::<BasicDomComponent>(FormMsg::Submit, |_| ()),
+            FormCmd::Submitted(Ok(..))
+        ));
+        assert_eq!(form.submit_count(), 2);
+    }
+
+    #[test]
+    fn it_cancels_pending_submit_by_input() {
+        let mut form = Form::<Person>::new()
+            .field("name", "Alex")
+            .validate_async("name", |_| async { Ok(()) })
+            .field("age", "20");
+
+        let _ = form.update::<BasicDomComponent>(FormMsg::Submit, |_| ());
+        input(&mut form, "name", "Bob");
+        assert!(!form.is_submitting());
+
+        assert!(matches!(validated(&mut form, 1), FormCmd::Cmd(..)));
+        assert!(matches!(validated(&mut form, 2), FormCmd::Cmd(..)));
+        assert!(!form.is_validating());
+    }
+}
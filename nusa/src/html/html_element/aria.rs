@@ -0,0 +1,194 @@
+//! Enumerated values of `role` and `aria-*` attributes.
+
+macro_rules! token {
+    ($(#[$attr:meta])* $name:ident { $($variant:ident => $value:tt),* $(,)? }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        pub enum $name {
+            $($variant),*
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value),*
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.as_str())
+            }
+        }
+    };
+}
+
+token!(
+    /// Values of `role`.
+    AriaRole {
+        Alert => "alert",
+        AlertDialog => "alertdialog",
+        Application => "application",
+        Article => "article",
+        Banner => "banner",
+        Blockquote => "blockquote",
+        Button => "button",
+        Caption => "caption",
+        Cell => "cell",
+        Checkbox => "checkbox",
+        Code => "code",
+        ColumnHeader => "columnheader",
+        Combobox => "combobox",
+        Complementary => "complementary",
+        ContentInfo => "contentinfo",
+        Definition => "definition",
+        Deletion => "deletion",
+        Dialog => "dialog",
+        Document => "document",
+        Emphasis => "emphasis",
+        Feed => "feed",
+        Figure => "figure",
+        Form => "form",
+        Generic => "generic",
+        Grid => "grid",
+        GridCell => "gridcell",
+        Group => "group",
+        Heading => "heading",
+        Img => "img",
+        Insertion => "insertion",
+        Link => "link",
+        List => "list",
+        Listbox => "listbox",
+        ListItem => "listitem",
+        Log => "log",
+        Main => "main",
+        Marquee => "marquee",
+        Math => "math",
+        Menu => "menu",
+        Menubar => "menubar",
+        MenuItem => "menuitem",
+        MenuItemCheckbox => "menuitemcheckbox",
+        MenuItemRadio => "menuitemradio",
+        Meter => "meter",
+        Navigation => "navigation",
+        None => "none",
+        Note => "note",
+        Option => "option",
+        Paragraph => "paragraph",
+        Presentation => "presentation",
+        ProgressBar => "progressbar",
+        Radio => "radio",
+        RadioGroup => "radiogroup",
+        Region => "region",
+        Row => "row",
+        RowGroup => "rowgroup",
+        RowHeader => "rowheader",
+        Scrollbar => "scrollbar",
+        Search => "search",
+        Searchbox => "searchbox",
+        Separator => "separator",
+        Slider => "slider",
+        SpinButton => "spinbutton",
+        Status => "status",
+        Strong => "strong",
+        Subscript => "subscript",
+        Superscript => "superscript",
+        Switch => "switch",
+        Tab => "tab",
+        Table => "table",
+        TabList => "tablist",
+        TabPanel => "tabpanel",
+        Term => "term",
+        Textbox => "textbox",
+        Time => "time",
+        Timer => "timer",
+        Toolbar => "toolbar",
+        Tooltip => "tooltip",
+        Tree => "tree",
+        TreeGrid => "treegrid",
+        TreeItem => "treeitem",
+    }
+);
+
+token!(
+    /// Values of `aria-autocomplete`.
+    AriaAutocomplete {
+        Inline => "inline",
+        List => "list",
+        Both => "both",
+        None => "none",
+    }
+);
+
+token!(
+    /// Values of `aria-current`.
+    AriaCurrent {
+        Page => "page",
+        Step => "step",
+        Location => "location",
+        Date => "date",
+        Time => "time",
+        True => "true",
+        False => "false",
+    }
+);
+
+token!(
+    /// Values of `aria-haspopup`.
+    AriaHasPopup {
+        False => "false",
+        True => "true",
+        Menu => "menu",
+        Listbox => "listbox",
+        Tree => "tree",
+        Grid => "grid",
+        Dialog => "dialog",
+    }
+);
+
+token!(
+    /// Values of `aria-invalid`.
+    AriaInvalid {
+        False => "false",
+        True => "true",
+        Grammar => "grammar",
+        Spelling => "spelling",
+    }
+);
+
+token!(
+    /// Values of `aria-live`.
+    AriaLive {
+        Off => "off",
+        Polite => "polite",
+        Assertive => "assertive",
+    }
+);
+
+token!(
+    /// Values of `aria-orientation`.
+    AriaOrientation {
+        Horizontal => "horizontal",
+        Vertical => "vertical",
+    }
+);
+
+token!(
+    /// Values of `aria-sort`.
+    AriaSort {
+        Ascending => "ascending",
+        Descending => "descending",
+        None => "none",
+        Other => "other",
+    }
+);
+
+token!(
+    /// Values of `aria-checked` and `aria-pressed`.
+    AriaTristate {
+        False => "false",
+        True => "true",
+        Mixed => "mixed",
+    }
+);
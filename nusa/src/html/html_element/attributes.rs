@@ -1,5 +1,11 @@
+use super::aria::{
+    AriaAutocomplete, AriaCurrent, AriaHasPopup, AriaInvalid, AriaLive, AriaOrientation, AriaRole,
+    AriaSort, AriaTristate,
+};
+use super::svg::{PathData, Transform};
 use crate::v_node::v_element::{VAttributeValue, VAttributeValues, VAttributes};
 use std::rc::Rc;
+use wasm_bindgen::JsValue;
 
 pub struct Attributes {
     index_id: Option<String>,
@@ -37,6 +43,7 @@ impl Attributes {
             let attr_values = VAttributeValues {
                 values: vec![value].into(),
                 delimiter: delimiter.into(),
+                namespace: None,
             };
             self.data.insert(name, attr_values);
         }
@@ -57,6 +64,7 @@ impl Attributes {
             let attr_values = VAttributeValues {
                 values: vec![].into(),
                 delimiter: delimiter.into(),
+                namespace: None,
             };
             self.data.insert(name, attr_values);
         }
@@ -88,13 +96,67 @@ impl Attributes {
         }
     }
 
+    /// Sets a DOM property like `checked`, `indeterminate` or `currentTime`.
+    /// It is compared with the live value of the element on every render.
+    /// It replaces other values of `name`. Values which are added to `name` after it make it an attribute.
+    /// If it is not set in the next render, the live value before the first write is restored.
+    pub fn property(mut self, name: impl Into<String>, value: JsValue) -> Self {
+        let attr_values = VAttributeValues {
+            values: vec![VAttributeValue::Property(Rc::new(value))].into(),
+            delimiter: String::new(),
+            namespace: None,
+        };
+        self.data.insert(name.into(), attr_values);
+        self
+    }
+
+    /// Sets `data-{name}`.
+    pub fn data(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.string(format!("data-{}", name.into()), value)
+    }
+
+    /// Sets `aria-{name}`. Use `aria_*` for known states and properties.
+    pub fn aria(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.string(format!("aria-{}", name.into()), value)
+    }
+
     pub fn style(self, style_name: impl Into<String>, value: impl Into<String>) -> Self {
         self.insert_with_delimiter(
             "style",
-            VAttributeValue::Str(Rc::new(format!("{}:{}", style_name.into(), value.into()))),
+            VAttributeValue::Style(Rc::new(style_name.into()), Rc::new(value.into())),
             ";",
         )
     }
+
+    /// Adds class names separated by whitespaces. A class name which is already added is ignored.
+    pub fn class(mut self, value: impl Into<String>) -> Self {
+        let value = value.into();
+        let mut class_names = self
+            .data
+            .get("class")
+            .map(|values| values.class_names())
+            .unwrap_or_default();
+
+        for class_name in value.split_whitespace() {
+            if !class_names.iter().any(|c| c == class_name) {
+                class_names.push(String::from(class_name));
+                self = self.insert(
+                    "class",
+                    VAttributeValue::Str(Rc::new(String::from(class_name))),
+                );
+            }
+        }
+
+        self
+    }
+
+    pub fn class_if(self, condition: bool, value: impl Into<String>) -> Self {
+        if condition {
+            self.class(value)
+        } else {
+            self
+        }
+    }
 }
 
 macro_rules! attr {
@@ -131,20 +193,279 @@ macro_rules! attr {
             }
         }
     };
+
+    ($name:tt : "true" | "false" as $f_name:ident) => {
+        pub fn $f_name(self, value: bool) -> Self {
+            self.string($name, if value { "true" } else { "false" })
+        }
+    };
+
+    ($name:tt : enum $t:ty as $f_name:ident) => {
+        pub fn $f_name(self, value: $t) -> Self {
+            self.string($name, value.as_str())
+        }
+    };
 }
 
 impl Attributes {
+    attr!("accept": String / "," as accept);
+    attr!("accept-charset": String / " " as accept_charset);
+    attr!("accesskey": String / " " as accesskey);
+    attr!("action": String / " " as action);
+    attr!("allow": String / ";" as allow);
+    attr!("alt": String / "" as alt);
+    attr!("async": bool / " " as async_);
+    attr!("autocapitalize": String / " " as autocapitalize);
+    attr!("autocomplete": String / " " as autocomplete);
+    attr!("autofocus": bool / " " as autofocus);
+    attr!("autoplay": bool / " " as autoplay);
+    attr!("capture": String / " " as capture);
+    attr!("charset": String / " " as charset);
     attr!("checked": bool / " " as checked);
-    attr!("class": String / " " as class);
+    attr!("cite": String / " " as cite);
+    attr!("cols": u64 / " " as cols);
+    attr!("colspan": u64 / " " as colspan);
+    attr!("content": String / "" as content);
+    attr!("contenteditable": String / " " as contenteditable);
+    attr!("controls": bool / " " as controls);
+    attr!("coords": String / "," as coords);
+    attr!("crossorigin": String / " " as crossorigin);
+    attr!("datetime": String / " " as datetime);
+    attr!("decoding": String / " " as decoding);
+    attr!("default": bool / " " as default);
+    attr!("defer": bool / " " as defer);
+    attr!("dir": String / " " as dir);
+    attr!("dirname": String / " " as dirname);
+    attr!("disabled": bool / " " as disabled);
+    attr!("download": String / "" as download);
     attr!("draggable": String / " " as draggable);
+    attr!("enctype": String / " " as enctype);
+    attr!("enterkeyhint": String / " " as enterkeyhint);
+    attr!("for": String / " " as for_);
+    attr!("form": String / " " as form);
+    attr!("formaction": String / " " as formaction);
+    attr!("formenctype": String / " " as formenctype);
+    attr!("formmethod": String / " " as formmethod);
+    attr!("formnovalidate": bool / " " as formnovalidate);
+    attr!("formtarget": String / " " as formtarget);
+    attr!("headers": String / " " as headers);
+    attr!("height": f64 / " " as height);
     attr!("hidden": bool / " " as hidden);
+    attr!("high": f64 / " " as high);
     attr!("href": String / " " as href);
+    attr!("hreflang": String / " " as hreflang);
+    attr!("http-equiv": String / " " as http_equiv);
     attr!("id": String / " " as id);
+    attr!("inert": bool / " " as inert);
+    attr!("inputmode": String / " " as inputmode);
+    attr!("integrity": String / " " as integrity);
+    attr!("is": String / " " as is);
+    attr!("ismap": bool / " " as ismap);
+    attr!("itemid": String / " " as itemid);
+    attr!("itemprop": String / " " as itemprop);
+    attr!("itemref": String / " " as itemref);
+    attr!("itemscope": bool / " " as itemscope);
+    attr!("itemtype": String / " " as itemtype);
+    attr!("kind": String / " " as kind);
+    attr!("label": String / "" as label);
+    attr!("lang": String / " " as lang);
+    attr!("list": String / " " as list);
+    attr!("loading": String / " " as loading);
+    attr!("loop": bool / " " as loop_);
+    attr!("low": f64 / " " as low);
+    attr!("max": String / " " as max);
+    attr!("maxlength": u64 / " " as maxlength);
+    attr!("media": String / "" as media);
+    attr!("method": String / " " as method);
+    attr!("min": String / " " as min);
+    attr!("minlength": u64 / " " as minlength);
+    attr!("multiple": bool / " " as multiple);
+    attr!("muted": bool / " " as muted);
+    attr!("name": String / " " as name);
+    attr!("nonce": String / " " as nonce);
+    attr!("novalidate": bool / " " as novalidate);
+    attr!("open": bool / " " as open);
+    attr!("optimum": f64 / " " as optimum);
+    attr!("part": String / " " as part);
+    attr!("pattern": String / "" as pattern);
+    attr!("ping": String / " " as ping);
     attr!("placeholder": String / "" as placeholder);
+    attr!("playsinline": bool / " " as playsinline);
+    attr!("poster": String / " " as poster);
+    attr!("preload": String / " " as preload);
+    attr!("readonly": bool / " " as readonly);
+    attr!("referrerpolicy": String / " " as referrerpolicy);
+    attr!("rel": String / " " as rel);
+    attr!("required": bool / " " as required);
+    attr!("reversed": bool / " " as reversed);
+    attr!("rows": u64 / " " as rows);
+    attr!("rowspan": u64 / " " as rowspan);
+    attr!("sandbox": String / " " as sandbox);
+    attr!("scope": String / " " as scope);
+    attr!("selected": bool / " " as selected);
+    attr!("shape": String / " " as shape);
+    attr!("size": u64 / " " as size);
+    attr!("sizes": String / "," as sizes);
+    attr!("slot": String / " " as slot);
+    attr!("span": u64 / " " as span);
+    attr!("spellcheck": String / " " as spellcheck);
     attr!("src": String / " " as src);
+    attr!("srcdoc": String / "" as srcdoc);
+    attr!("srclang": String / " " as srclang);
+    attr!("srcset": String / "," as srcset);
+    attr!("start": i64 / " " as start);
+    attr!("step": String / " " as step);
+    attr!("tabindex": i64 / " " as tabindex);
+    attr!("target": String / " " as target);
     attr!("title": String / " " as title);
+    attr!("translate": String / " " as translate);
     attr!("type": String / " " as type_);
+    attr!("usemap": String / " " as usemap);
     attr!("value": String / " " as value);
+    attr!("width": f64 / " " as width);
+    attr!("wrap": String / " " as wrap);
+}
+
+impl Attributes {
+    attr!("role": enum AriaRole as role);
+
+    attr!("aria-activedescendant": String / " " as aria_activedescendant);
+    attr!("aria-atomic": "true" | "false" as aria_atomic);
+    attr!("aria-autocomplete": enum AriaAutocomplete as aria_autocomplete);
+    attr!("aria-busy": "true" | "false" as aria_busy);
+    attr!("aria-checked": enum AriaTristate as aria_checked);
+    attr!("aria-colcount": i64 / " " as aria_colcount);
+    attr!("aria-colindex": u64 / " " as aria_colindex);
+    attr!("aria-colspan": u64 / " " as aria_colspan);
+    attr!("aria-controls": String / " " as aria_controls);
+    attr!("aria-current": enum AriaCurrent as aria_current);
+    attr!("aria-describedby": String / " " as aria_describedby);
+    attr!("aria-details": String / " " as aria_details);
+    attr!("aria-disabled": "true" | "false" as aria_disabled);
+    attr!("aria-errormessage": String / " " as aria_errormessage);
+    attr!("aria-expanded": "true" | "false" as aria_expanded);
+    attr!("aria-flowto": String / " " as aria_flowto);
+    attr!("aria-haspopup": enum AriaHasPopup as aria_haspopup);
+    attr!("aria-hidden": "true" | "false" as aria_hidden);
+    attr!("aria-invalid": enum AriaInvalid as aria_invalid);
+    attr!("aria-keyshortcuts": String / " " as aria_keyshortcuts);
+    attr!("aria-label": String / "" as aria_label);
+    attr!("aria-labelledby": String / " " as aria_labelledby);
+    attr!("aria-level": u64 / " " as aria_level);
+    attr!("aria-live": enum AriaLive as aria_live);
+    attr!("aria-modal": "true" | "false" as aria_modal);
+    attr!("aria-multiline": "true" | "false" as aria_multiline);
+    attr!("aria-multiselectable": "true" | "false" as aria_multiselectable);
+    attr!("aria-orientation": enum AriaOrientation as aria_orientation);
+    attr!("aria-owns": String / " " as aria_owns);
+    attr!("aria-placeholder": String / "" as aria_placeholder);
+    attr!("aria-posinset": u64 / " " as aria_posinset);
+    attr!("aria-pressed": enum AriaTristate as aria_pressed);
+    attr!("aria-readonly": "true" | "false" as aria_readonly);
+    attr!("aria-relevant": String / " " as aria_relevant);
+    attr!("aria-required": "true" | "false" as aria_required);
+    attr!("aria-roledescription": String / "" as aria_roledescription);
+    attr!("aria-rowcount": i64 / " " as aria_rowcount);
+    attr!("aria-rowindex": u64 / " " as aria_rowindex);
+    attr!("aria-rowspan": u64 / " " as aria_rowspan);
+    attr!("aria-selected": "true" | "false" as aria_selected);
+    attr!("aria-setsize": i64 / " " as aria_setsize);
+    attr!("aria-sort": enum AriaSort as aria_sort);
+    attr!("aria-valuemax": f64 / " " as aria_valuemax);
+    attr!("aria-valuemin": f64 / " " as aria_valuemin);
+    attr!("aria-valuenow": f64 / " " as aria_valuenow);
+    attr!("aria-valuetext": String / "" as aria_valuetext);
+}
+
+/// SVG presentation and geometry attributes.
+impl Attributes {
+    attr!("clip-path": String / " " as clip_path);
+    attr!("clip-rule": String / " " as clip_rule);
+    attr!("cx": f64 / " " as cx);
+    attr!("cy": f64 / " " as cy);
+    attr!("dominant-baseline": String / " " as dominant_baseline);
+    attr!("dx": f64 / " " as dx);
+    attr!("dy": f64 / " " as dy);
+    attr!("fill": String / " " as fill);
+    attr!("fill-opacity": f64 / " " as fill_opacity);
+    attr!("fill-rule": String / " " as fill_rule);
+    attr!("filter": String / " " as filter);
+    attr!("font-family": String / " " as font_family);
+    attr!("font-size": String / " " as font_size);
+    attr!("font-weight": String / " " as font_weight);
+    attr!("fx": f64 / " " as fx);
+    attr!("fy": f64 / " " as fy);
+    attr!("gradientUnits": String / " " as gradient_units);
+    attr!("marker-end": String / " " as marker_end);
+    attr!("marker-mid": String / " " as marker_mid);
+    attr!("marker-start": String / " " as marker_start);
+    attr!("mask": String / " " as mask);
+    attr!("offset": String / " " as offset);
+    attr!("opacity": f64 / " " as opacity);
+    attr!("pathLength": f64 / " " as path_length);
+    attr!("patternUnits": String / " " as pattern_units);
+    attr!("preserveAspectRatio": String / " " as preserve_aspect_ratio);
+    attr!("r": f64 / " " as r);
+    attr!("rx": f64 / " " as rx);
+    attr!("ry": f64 / " " as ry);
+    attr!("spreadMethod": String / " " as spread_method);
+    attr!("stdDeviation": f64 / " " as std_deviation);
+    attr!("stop-color": String / " " as stop_color);
+    attr!("stop-opacity": f64 / " " as stop_opacity);
+    attr!("stroke": String / " " as stroke);
+    attr!("stroke-dasharray": String / " " as stroke_dasharray);
+    attr!("stroke-dashoffset": f64 / " " as stroke_dashoffset);
+    attr!("stroke-linecap": String / " " as stroke_linecap);
+    attr!("stroke-linejoin": String / " " as stroke_linejoin);
+    attr!("stroke-miterlimit": f64 / " " as stroke_miterlimit);
+    attr!("stroke-opacity": f64 / " " as stroke_opacity);
+    attr!("stroke-width": f64 / " " as stroke_width);
+    attr!("text-anchor": String / " " as text_anchor);
+    attr!("vector-effect": String / " " as vector_effect);
+    attr!("visibility": String / " " as visibility);
+    attr!("x": f64 / " " as x);
+    attr!("x1": f64 / " " as x1);
+    attr!("x2": f64 / " " as x2);
+    attr!("y": f64 / " " as y);
+    attr!("y1": f64 / " " as y1);
+    attr!("y2": f64 / " " as y2);
+
+    /// `viewBox`
+    pub fn view_box(self, min_x: f64, min_y: f64, width: f64, height: f64) -> Self {
+        self.string(
+            "viewBox",
+            format!("{} {} {} {}", min_x, min_y, width, height),
+        )
+    }
+
+    /// `points` of `polyline` and `polygon`.
+    pub fn points(self, points: &[(f64, f64)]) -> Self {
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect::<Vec<_>>();
+        self.string("points", points.join(" "))
+    }
+
+    pub fn d(self, d: PathData) -> Self {
+        self.insert_with_delimiter("d", VAttributeValue::Str(Rc::new(d.to_string())), " ")
+    }
+
+    pub fn transform(self, transform: Transform) -> Self {
+        self.insert_with_delimiter(
+            "transform",
+            VAttributeValue::Str(Rc::new(transform.to_string())),
+            " ",
+        )
+    }
+
+    pub fn gradient_transform(self, transform: Transform) -> Self {
+        self.insert_with_delimiter(
+            "gradientTransform",
+            VAttributeValue::Str(Rc::new(transform.to_string())),
+            " ",
+        )
+    }
 }
 
 impl std::default::Default for Attributes {
@@ -152,3 +473,38 @@ impl std::default::Default for Attributes {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sets_typed_attributes() {
+        let (_, attrs) = Attributes::new()
+            .role(AriaRole::Button)
+            .aria_pressed(AriaTristate::Mixed)
+            .aria_hidden(false)
+            .data("item-id", "42")
+            .disabled(true)
+            .readonly(false)
+            .tabindex(-1)
+            .into_attributes();
+
+        let attrs = attrs
+            .iter()
+            .map(|(name, values)| (name.as_str(), values.to_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            attrs,
+            vec![
+                ("role", String::from("button")),
+                ("aria-pressed", String::from("mixed")),
+                ("aria-hidden", String::from("false")),
+                ("data-item-id", String::from("42")),
+                ("disabled", String::from("")),
+                ("tabindex", String::from("-1")),
+            ]
+        );
+    }
+}
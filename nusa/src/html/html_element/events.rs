@@ -1,7 +1,12 @@
-use crate::v_node::v_element::{VEvent, VEventHandlers, VEvents, VReferHandler};
+use super::form_value::{self, FormValueError};
+use crate::dom_ref::NodeRef;
+use crate::v_node::v_element::{
+    VEvent, VEventHandler, VEventHandlers, VEvents, VListenerOptions, VNodeRef, VReferHandler,
+};
 use kagura::node::{BasicNodeMsg, Msg};
 use kagura::Component;
-use wasm_bindgen::JsCast;
+use std::any::TypeId;
+use wasm_bindgen::{JsCast, JsValue};
 
 pub struct Events {
     events: VEvents,
@@ -20,48 +25,128 @@ impl Events {
         }
     }
 
+    /// Handles an event on the root element, to which handlers are delegated.
+    /// Events which do not bubble, like `focus`, `blur`, `scroll` and `mouseenter`, need `direct`.
+    /// Their typed methods, like `on_focus`, add it by default.
     pub fn on<Target: Component + 'static>(
+        self,
+        type_: impl Into<String>,
+        target: &Target,
+        handler: impl FnMut(VEvent<web_sys::Event>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.on_with(type_, target, handler)
+    }
+
+    pub fn capture_on<Target: Component + 'static>(
+        self,
+        type_: impl Into<String>,
+        target: &Target,
+        handler: impl FnMut(VEvent<web_sys::Event>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.capture_on_with(type_, target, handler)
+    }
+
+    /// Handles an event as `E`.
+    /// The handler is not called if the event is not an instance of `E`, and it is warned in debug builds.
+    pub fn on_with<E: JsCast + 'static, Target: Component + 'static>(
         mut self,
         type_: impl Into<String>,
         target: &Target,
-        handler: impl FnOnce(VEvent<web_sys::Event>) -> Target::Msg + 'static,
+        handler: impl FnMut(VEvent<E>) -> Target::Msg + 'static,
     ) -> Self {
         let type_ = type_.into();
-        let target_id = Msg::target_id(target);
-        let mut handelrs = if let Some(handlers) = self.events.events.remove(&type_) {
-            handlers
-        } else {
-            VEventHandlers::new()
-        };
-        handelrs.bubbles.push(Box::new(move |e| {
-            let msg = handler(e);
-            let msg = BasicNodeMsg::<Target>::ComponentMsg(msg);
-            Msg::new(target_id, Box::new(msg))
-        }));
-        self.events.events.insert(type_, handelrs);
+        let handler = Self::wrap_handler(&type_, target, handler);
+        self.handlers(type_).bubbles.push(handler);
         self
     }
 
-    pub fn capture_on<Target: Component + 'static>(
+    /// Handles an event as `E` in capture phase.
+    /// The handler is not called if the event is not an instance of `E`, and it is warned in debug builds.
+    pub fn capture_on_with<E: JsCast + 'static, Target: Component + 'static>(
         mut self,
         type_: impl Into<String>,
         target: &Target,
-        handler: impl FnOnce(VEvent<web_sys::Event>) -> Target::Msg + 'static,
+        handler: impl FnMut(VEvent<E>) -> Target::Msg + 'static,
     ) -> Self {
         let type_ = type_.into();
+        let handler = Self::wrap_handler(&type_, target, handler);
+        self.handlers(type_).captures.push(handler);
+        self
+    }
+
+    /// Adds handlers of `type_` to the element directly, not to the root element.
+    /// It is needed for events which do not bubble, or to use `passive` or `once`.
+    /// Typed methods of `load`, `focus`, `blur`, `scroll`, `toggle`, `mouseenter`, `mouseleave`,
+    /// `pointerenter`, `pointerleave` and media events call it by default.
+    pub fn direct(mut self, type_: impl Into<String>, options: VListenerOptions) -> Self {
+        self.handlers(type_.into()).direct = Some(options);
+        self
+    }
+
+    /// Adds handlers of `type_` to the element directly as passive listeners.
+    pub fn passive(mut self, type_: impl Into<String>) -> Self {
+        let handlers = self.handlers(type_.into());
+        let mut options = handlers.direct.unwrap_or_default();
+        options.passive = true;
+        handlers.direct = Some(options);
+        self
+    }
+
+    /// Adds handlers of `type_` to the element directly, and removes them after the first event.
+    pub fn once(mut self, type_: impl Into<String>) -> Self {
+        let handlers = self.handlers(type_.into());
+        let mut options = handlers.direct.unwrap_or_default();
+        options.once = true;
+        handlers.direct = Some(options);
+        self
+    }
+
+    /// Keeps options which are set by `direct`, `passive` or `once`.
+    fn direct_by_default(mut self, type_: &str) -> Self {
+        let handlers = self.handlers(String::from(type_));
+        if handlers.direct.is_none() {
+            handlers.direct = Some(VListenerOptions::default());
+        }
+        self
+    }
+
+    fn handlers(&mut self, type_: String) -> &mut VEventHandlers {
+        self.events
+            .events
+            .entry(type_)
+            .or_insert_with(VEventHandlers::new)
+    }
+
+    fn wrap_handler<E: JsCast + 'static, Target: Component + 'static>(
+        type_: &str,
+        target: &Target,
+        mut handler: impl FnMut(VEvent<E>) -> Target::Msg + 'static,
+    ) -> VEventHandler {
         let target_id = Msg::target_id(target);
-        let mut handelrs = if let Some(handlers) = self.events.events.remove(&type_) {
-            handlers
-        } else {
-            VEventHandlers::new()
-        };
-        handelrs.captures.push(Box::new(move |e| {
+        let type_ = String::from(type_);
+        Box::new(move |e| {
+            // every dispatched event is a `web_sys::Event`, so it is not checked.
+            let e = if TypeId::of::<E>() == TypeId::of::<web_sys::Event>() {
+                e.unchecked_into::<E>()
+            } else {
+                match e.dyn_into::<E>() {
+                    Ok(e) => e,
+                    Err(_) => {
+                        if cfg!(debug_assertions) {
+                            web_sys::console::warn_1(&JsValue::from(format!(
+                                "a handler of `{}` is skipped: the event is not `{}`",
+                                type_,
+                                std::any::type_name::<E>()
+                            )));
+                        }
+                        return None;
+                    }
+                }
+            };
             let msg = handler(e);
             let msg = BasicNodeMsg::<Target>::ComponentMsg(msg);
-            Msg::new(target_id, Box::new(msg))
-        }));
-        self.events.events.insert(type_, handelrs);
-        self
+            Some(Msg::new(target_id, Box::new(msg)))
+        })
     }
 
     pub fn refer<Target: Component + 'static>(
@@ -80,69 +165,261 @@ impl Events {
         self
     }
 
+    /// Sets the element to `node_ref` while it is rendered.
+    pub fn node_ref<T: JsCast>(mut self, node_ref: &NodeRef<T>) -> Self {
+        self.events
+            .node_refs
+            .push(VNodeRef::new(node_ref.cell(), None, None));
+        self
+    }
+
+    /// Like `node_ref`, and sends a message when the element is attached to `node_ref` and detached from it.
+    pub fn node_ref_with<Target: Component + 'static, T: JsCast + 'static>(
+        mut self,
+        target: &Target,
+        node_ref: &NodeRef<T>,
+        on_attach: impl FnOnce(T) -> Target::Msg + 'static,
+        on_detach: impl FnOnce() -> Target::Msg + 'static,
+    ) -> Self {
+        let target_id = Msg::target_id(target);
+        let on_attach = Box::new(move |node: web_sys::Node| {
+            let msg = on_attach(node.dyn_into().ok()?);
+            let msg = BasicNodeMsg::<Target>::ComponentMsg(msg);
+            Some(Msg::new(target_id, Box::new(msg)))
+        });
+        let on_detach = Box::new(move || {
+            let msg = BasicNodeMsg::<Target>::ComponentMsg(on_detach());
+            Msg::new(target_id, Box::new(msg))
+        });
+        self.events.node_refs.push(VNodeRef::new(
+            node_ref.cell(),
+            Some(on_attach),
+            Some(on_detach),
+        ));
+        self
+    }
+
     pub fn on_input<Target: Component + 'static>(
         self,
         target: &Target,
-        handler: impl FnOnce(String) -> Target::Msg + 'static,
+        mut handler: impl FnMut(String) -> Target::Msg + 'static,
     ) -> Self {
         self.on("input", target, move |e| {
-            if let Some(target) = e.target() {
-                if let Some(target) = target.dyn_ref::<web_sys::HtmlInputElement>() {
-                    return handler(target.value());
-                } else if let Some(target) = target.dyn_ref::<web_sys::HtmlTextAreaElement>() {
-                    return handler(target.value());
-                }
-            }
-            handler(String::new())
+            handler(form_value::value(e.event_target()).unwrap_or_default())
+        })
+    }
+
+    /// Parses `value` on `input` event.
+    pub fn on_input_parse<T: std::str::FromStr, Target: Component + 'static>(
+        self,
+        target: &Target,
+        mut handler: impl FnMut(Result<T, FormValueError>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.on("input", target, move |e| {
+            handler(form_value::parse(e.event_target()))
+        })
+    }
+
+    pub fn on_input_number<Target: Component + 'static>(
+        self,
+        target: &Target,
+        handler: impl FnMut(Result<f64, FormValueError>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.on_input_parse(target, handler)
+    }
+
+    /// `checked` of checkbox or radio on `change` event.
+    pub fn on_checked<Target: Component + 'static>(
+        self,
+        target: &Target,
+        mut handler: impl FnMut(Result<bool, FormValueError>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.on("change", target, move |e| {
+            handler(form_value::checked(e.event_target()))
+        })
+    }
+
+    /// `value` of `select` on `change` event.
+    pub fn on_select_change<Target: Component + 'static>(
+        self,
+        target: &Target,
+        mut handler: impl FnMut(Result<String, FormValueError>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.on("change", target, move |e| {
+            handler(form_value::value(e.event_target()))
+        })
+    }
+
+    /// Values of selected options of `select multiple` on `change` event.
+    pub fn on_select_multiple_change<Target: Component + 'static>(
+        self,
+        target: &Target,
+        mut handler: impl FnMut(Result<Vec<String>, FormValueError>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.on("change", target, move |e| {
+            handler(form_value::selected_values(e.event_target()))
+        })
+    }
+
+    /// Files of `input type="file"` on `change` event.
+    pub fn on_files<Target: Component + 'static>(
+        self,
+        target: &Target,
+        mut handler: impl FnMut(Result<Vec<web_sys::File>, FormValueError>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.on("change", target, move |e| {
+            handler(form_value::files(e.event_target()))
+        })
+    }
+
+    /// `innerText` of a `contenteditable` element on `input` event.
+    pub fn on_contenteditable_input<Target: Component + 'static>(
+        self,
+        target: &Target,
+        mut handler: impl FnMut(Result<String, FormValueError>) -> Target::Msg + 'static,
+    ) -> Self {
+        self.on("input", target, move |e| {
+            handler(form_value::content_editable_text(e.event_target()))
         })
     }
 }
 
 macro_rules! event_type {
-    ($event_ty:tt as $b_name:ident / $c_name:ident) => {
+    ($event_ty:tt: $data_ty:ty as $b_name:ident / $c_name:ident) => {
+        pub fn $b_name<Target: Component + 'static>(
+            self,
+            target: &Target,
+            handler: impl FnMut(VEvent<$data_ty>) -> Target::Msg + 'static,
+        ) -> Self {
+            self.on_with($event_ty, target, handler)
+        }
+
+        pub fn $c_name<Target: Component + 'static>(
+            self,
+            target: &Target,
+            handler: impl FnMut(VEvent<$data_ty>) -> Target::Msg + 'static,
+        ) -> Self {
+            self.capture_on_with($event_ty, target, handler)
+        }
+    };
+
+    ($event_ty:tt: $data_ty:ty as $b_name:ident / $c_name:ident, direct) => {
         pub fn $b_name<Target: Component + 'static>(
             self,
             target: &Target,
-            handler: impl FnOnce(VEvent<web_sys::Event>) -> Target::Msg + 'static,
+            handler: impl FnMut(VEvent<$data_ty>) -> Target::Msg + 'static,
         ) -> Self {
-            self.on($event_ty, target, handler)
+            self.on_with($event_ty, target, handler)
+                .direct_by_default($event_ty)
         }
 
         pub fn $c_name<Target: Component + 'static>(
             self,
             target: &Target,
-            handler: impl FnOnce(VEvent<web_sys::Event>) -> Target::Msg + 'static,
+            handler: impl FnMut(VEvent<$data_ty>) -> Target::Msg + 'static,
         ) -> Self {
-            self.capture_on($event_ty, target, handler)
+            self.capture_on_with($event_ty, target, handler)
+                .direct_by_default($event_ty)
         }
     };
 }
 
+/// Handlers of events which do not bubble, like `mouseenter`, `focus`, `scroll` and media events,
+/// are added to the element directly.
 impl Events {
-    event_type!("load" as on_load / capture_on_load);
-
-    event_type!("drag" as on_drag / capture_on_drag);
-    event_type!("dragend" as on_dragend / capture_on_dragend);
-    event_type!("dragenter" as on_dragenter / capture_on_dragenter);
-    event_type!("dragstart" as on_dragstart / capture_on_dragstart);
-    event_type!("dragleave" as on_dragleave / capture_on_dragleave);
-    event_type!("dragover" as on_dragover / capture_on_dragover);
-    event_type!("drop" as on_drop / capture_on_drop);
-
-    event_type!("click" as on_click / capture_on_click);
-    event_type!("contextmenu" as on_contextmenu / capture_on_contextmenu);
-    event_type!("dblclick" as on_dblclick / capture_on_dblclick);
-    event_type!("mousedown" as on_mousedown / capture_on_mousedown);
-    event_type!("mouseenter" as on_mouseenter / capture_on_mouseenter);
-    event_type!("mouseleave" as on_mouseleave / capture_on_mouseleave);
-    event_type!("mousemove" as on_mousemove / capture_on_mousemove);
-    event_type!("mouseover" as on_mouseover / capture_on_mouseover);
-    event_type!("mouseout" as on_mouseout / capture_on_mouseout);
-    event_type!("mouseup" as on_mouseup / capture_on_mouseup);
-
-    event_type!("keydown" as on_keydown / capture_on_keydown);
-    event_type!("keypress" as on_keypress / capture_on_keypress);
-    event_type!("keyup" as on_keyup / capture_on_keyup);
+    event_type!("load": web_sys::Event as on_load / capture_on_load, direct);
+
+    event_type!("drag": web_sys::DragEvent as on_drag / capture_on_drag);
+    event_type!("dragend": web_sys::DragEvent as on_dragend / capture_on_dragend);
+    event_type!("dragenter": web_sys::DragEvent as on_dragenter / capture_on_dragenter);
+    event_type!("dragstart": web_sys::DragEvent as on_dragstart / capture_on_dragstart);
+    event_type!("dragleave": web_sys::DragEvent as on_dragleave / capture_on_dragleave);
+    event_type!("dragover": web_sys::DragEvent as on_dragover / capture_on_dragover);
+    event_type!("drop": web_sys::DragEvent as on_drop / capture_on_drop);
+
+    event_type!("click": web_sys::MouseEvent as on_click / capture_on_click);
+    event_type!("contextmenu": web_sys::MouseEvent as on_contextmenu / capture_on_contextmenu);
+    event_type!("dblclick": web_sys::MouseEvent as on_dblclick / capture_on_dblclick);
+    event_type!("mousedown": web_sys::MouseEvent as on_mousedown / capture_on_mousedown);
+    event_type!("mouseenter": web_sys::MouseEvent as on_mouseenter / capture_on_mouseenter, direct);
+    event_type!("mouseleave": web_sys::MouseEvent as on_mouseleave / capture_on_mouseleave, direct);
+    event_type!("mousemove": web_sys::MouseEvent as on_mousemove / capture_on_mousemove);
+    event_type!("mouseover": web_sys::MouseEvent as on_mouseover / capture_on_mouseover);
+    event_type!("mouseout": web_sys::MouseEvent as on_mouseout / capture_on_mouseout);
+    event_type!("mouseup": web_sys::MouseEvent as on_mouseup / capture_on_mouseup);
+
+    event_type!("keydown": web_sys::KeyboardEvent as on_keydown / capture_on_keydown);
+    event_type!("keypress": web_sys::KeyboardEvent as on_keypress / capture_on_keypress);
+    event_type!("keyup": web_sys::KeyboardEvent as on_keyup / capture_on_keyup);
+
+    event_type!("pointercancel": web_sys::PointerEvent as on_pointercancel / capture_on_pointercancel);
+    event_type!("pointerdown": web_sys::PointerEvent as on_pointerdown / capture_on_pointerdown);
+    event_type!("pointerenter": web_sys::PointerEvent as on_pointerenter / capture_on_pointerenter, direct);
+    event_type!("pointerleave": web_sys::PointerEvent as on_pointerleave / capture_on_pointerleave, direct);
+    event_type!("pointermove": web_sys::PointerEvent as on_pointermove / capture_on_pointermove);
+    event_type!("pointerout": web_sys::PointerEvent as on_pointerout / capture_on_pointerout);
+    event_type!("pointerover": web_sys::PointerEvent as on_pointerover / capture_on_pointerover);
+    event_type!("pointerup": web_sys::PointerEvent as on_pointerup / capture_on_pointerup);
+
+    event_type!("wheel": web_sys::WheelEvent as on_wheel / capture_on_wheel);
+
+    event_type!("blur": web_sys::FocusEvent as on_blur / capture_on_blur, direct);
+    event_type!("focus": web_sys::FocusEvent as on_focus / capture_on_focus, direct);
+    event_type!("focusin": web_sys::FocusEvent as on_focusin / capture_on_focusin);
+    event_type!("focusout": web_sys::FocusEvent as on_focusout / capture_on_focusout);
+
+    event_type!("touchcancel": web_sys::TouchEvent as on_touchcancel / capture_on_touchcancel);
+    event_type!("touchend": web_sys::TouchEvent as on_touchend / capture_on_touchend);
+    event_type!("touchmove": web_sys::TouchEvent as on_touchmove / capture_on_touchmove);
+    event_type!("touchstart": web_sys::TouchEvent as on_touchstart / capture_on_touchstart);
+
+    event_type!("change": web_sys::Event as on_change / capture_on_change);
+    event_type!("reset": web_sys::Event as on_reset / capture_on_reset);
+    event_type!("submit": web_sys::SubmitEvent as on_submit / capture_on_submit);
+
+    event_type!("scroll": web_sys::Event as on_scroll / capture_on_scroll, direct);
+    event_type!("toggle": web_sys::Event as on_toggle / capture_on_toggle, direct);
+
+    event_type!("compositionend": web_sys::CompositionEvent as on_compositionend / capture_on_compositionend);
+    event_type!("compositionstart": web_sys::CompositionEvent as on_compositionstart / capture_on_compositionstart);
+    event_type!("compositionupdate": web_sys::CompositionEvent as on_compositionupdate / capture_on_compositionupdate);
+
+    event_type!("copy": web_sys::ClipboardEvent as on_copy / capture_on_copy);
+    event_type!("cut": web_sys::ClipboardEvent as on_cut / capture_on_cut);
+    event_type!("paste": web_sys::ClipboardEvent as on_paste / capture_on_paste);
+
+    event_type!("animationcancel": web_sys::AnimationEvent as on_animationcancel / capture_on_animationcancel);
+    event_type!("animationend": web_sys::AnimationEvent as on_animationend / capture_on_animationend);
+    event_type!("animationiteration": web_sys::AnimationEvent as on_animationiteration / capture_on_animationiteration);
+    event_type!("animationstart": web_sys::AnimationEvent as on_animationstart / capture_on_animationstart);
+
+    event_type!("transitioncancel": web_sys::TransitionEvent as on_transitioncancel / capture_on_transitioncancel);
+    event_type!("transitionend": web_sys::TransitionEvent as on_transitionend / capture_on_transitionend);
+    event_type!("transitionrun": web_sys::TransitionEvent as on_transitionrun / capture_on_transitionrun);
+    event_type!("transitionstart": web_sys::TransitionEvent as on_transitionstart / capture_on_transitionstart);
+
+    event_type!("abort": web_sys::Event as on_abort / capture_on_abort, direct);
+    event_type!("canplay": web_sys::Event as on_canplay / capture_on_canplay, direct);
+    event_type!("canplaythrough": web_sys::Event as on_canplaythrough / capture_on_canplaythrough, direct);
+    event_type!("durationchange": web_sys::Event as on_durationchange / capture_on_durationchange, direct);
+    event_type!("emptied": web_sys::Event as on_emptied / capture_on_emptied, direct);
+    event_type!("ended": web_sys::Event as on_ended / capture_on_ended, direct);
+    event_type!("error": web_sys::Event as on_error / capture_on_error, direct);
+    event_type!("loadeddata": web_sys::Event as on_loadeddata / capture_on_loadeddata, direct);
+    event_type!("loadedmetadata": web_sys::Event as on_loadedmetadata / capture_on_loadedmetadata, direct);
+    event_type!("loadstart": web_sys::Event as on_loadstart / capture_on_loadstart, direct);
+    event_type!("pause": web_sys::Event as on_pause / capture_on_pause, direct);
+    event_type!("play": web_sys::Event as on_play / capture_on_play, direct);
+    event_type!("playing": web_sys::Event as on_playing / capture_on_playing, direct);
+    event_type!("progress": web_sys::Event as on_progress / capture_on_progress, direct);
+    event_type!("ratechange": web_sys::Event as on_ratechange / capture_on_ratechange, direct);
+    event_type!("seeked": web_sys::Event as on_seeked / capture_on_seeked, direct);
+    event_type!("seeking": web_sys::Event as on_seeking / capture_on_seeking, direct);
+    event_type!("stalled": web_sys::Event as on_stalled / capture_on_stalled, direct);
+    event_type!("suspend": web_sys::Event as on_suspend / capture_on_suspend, direct);
+    event_type!("timeupdate": web_sys::Event as on_timeupdate / capture_on_timeupdate, direct);
+    event_type!("volumechange": web_sys::Event as on_volumechange / capture_on_volumechange, direct);
+    event_type!("waiting": web_sys::Event as on_waiting / capture_on_waiting, direct);
 }
 
 impl std::default::Default for Events {
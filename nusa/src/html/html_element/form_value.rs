@@ -0,0 +1,208 @@
+//! Typed values of form controls, which are used by `Events::on_input_number` and so on.
+//! Values are read through `DomBackend`, so they work without browser.
+
+use crate::v_node::VEventTarget;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum FormValueError {
+    /// The event has no target.
+    NoTarget,
+    /// The target is not a form control which has the value. It has the tag name of the target.
+    UnsupportedElement(String),
+    /// The value can not be parsed. It has the raw value.
+    Parse(String),
+}
+
+impl std::fmt::Display for FormValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoTarget => write!(f, "event has no target"),
+            Self::UnsupportedElement(tag_name) => {
+                write!(f, "<{}> is not a supported form control", tag_name)
+            }
+            Self::Parse(value) => write!(f, "failed to parse \"{}\"", value),
+        }
+    }
+}
+
+impl std::error::Error for FormValueError {}
+
+/// `value` of `input`, `textarea` or `select`.
+pub fn value(target: Option<VEventTarget>) -> Result<String, FormValueError> {
+    let target = target.ok_or(FormValueError::NoTarget)?;
+    match target.tag_name().as_deref() {
+        Some("input" | "textarea" | "select") => target.value().ok_or_else(|| unsupported(&target)),
+        _ => Err(unsupported(&target)),
+    }
+}
+
+/// Parses `value` of `input`, `textarea` or `select`. Surrounding whitespaces are ignored.
+pub fn parse<T: std::str::FromStr>(target: Option<VEventTarget>) -> Result<T, FormValueError> {
+    let value = value(target)?;
+    value
+        .trim()
+        .parse()
+        .map_err(|_| FormValueError::Parse(value))
+}
+
+/// `checked` of `input type="checkbox"` or `input type="radio"`.
+pub fn checked(target: Option<VEventTarget>) -> Result<bool, FormValueError> {
+    let target = target.ok_or(FormValueError::NoTarget)?;
+    target.checked().ok_or_else(|| unsupported(&target))
+}
+
+/// Values of selected options of `select`.
+pub fn selected_values(target: Option<VEventTarget>) -> Result<Vec<String>, FormValueError> {
+    let target = target.ok_or(FormValueError::NoTarget)?;
+    target.selected_values().ok_or_else(|| unsupported(&target))
+}
+
+/// Files of `input type="file"`.
+pub fn files(target: Option<VEventTarget>) -> Result<Vec<web_sys::File>, FormValueError> {
+    let target = target.ok_or(FormValueError::NoTarget)?;
+    target.files().ok_or_else(|| unsupported(&target))
+}
+
+/// `innerText` of an element whose `contenteditable` is enabled.
+pub fn content_editable_text(target: Option<VEventTarget>) -> Result<String, FormValueError> {
+    let target = target.ok_or(FormValueError::NoTarget)?;
+    target
+        .content_editable_text()
+        .ok_or_else(|| unsupported(&target))
+}
+
+fn unsupported(target: &VEventTarget) -> FormValueError {
+    FormValueError::UnsupportedElement(target.tag_name().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dom_backend::MemoryDomBackend;
+    use crate::dom_node::BasicDomNode;
+    use crate::html::html_element::{Attributes, Events};
+    use crate::Html;
+    use kagura::node::RenderNode;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Results = Rc<RefCell<Vec<String>>>;
+
+    fn record<T: std::fmt::Debug>(results: &Results) -> impl FnMut(T) + 'static {
+        let results = Rc::clone(results);
+        move |result| results.borrow_mut().push(format!("{:?}", result))
+    }
+
+    #[test]
+    fn it_reads_values_of_form_controls() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let results = Results::default();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let results = Rc::clone(&results);
+            move |this| {
+                let option = |value: &str, attrs: Attributes| {
+                    Html::option(attrs.value(value), Events::new(), vec![])
+                };
+                vec![
+                    Html::input(
+                        Attributes::new(),
+                        Events::new().on_input_number(this, record(&results)),
+                        vec![],
+                    ),
+                    Html::input(
+                        Attributes::new().type_("checkbox"),
+                        Events::new().on_checked(this, record(&results)),
+                        vec![],
+                    ),
+                    Html::select(
+                        Attributes::new().multiple(true),
+                        Events::new()
+                            .on_select_change(this, record(&results))
+                            .on_select_multiple_change(this, record(&results)),
+                        vec![
+                            option("a", Attributes::new().selected(true)),
+                            option("b", Attributes::new()),
+                            option("c", Attributes::new()),
+                        ],
+                    ),
+                ]
+            }
+        });
+        node.render();
+        let children = root.children();
+        let (number, checkbox, select) = (&children[0], &children[1], &children[2]);
+
+        number.set_value(" 1.5 ");
+        backend.dispatch_event(number, "input", true);
+        number.set_value("1.5x");
+        backend.dispatch_event(number, "input", true);
+
+        checkbox.set_checked(true);
+        backend.dispatch_event(checkbox, "change", true);
+
+        select.set_value("a");
+        select.children()[2].set_checked(true);
+        backend.dispatch_event(select, "change", true);
+
+        assert_eq!(
+            *results.borrow(),
+            vec![
+                r#"Ok(1.5)"#,
+                r#"Err(Parse("1.5x"))"#,
+                r#"Ok(true)"#,
+                r#"Ok("a")"#,
+                r#"Ok(["a", "c"])"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn it_rejects_unsupported_elements() {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        let results = Results::default();
+        let mut node = BasicDomNode::with_backend(backend.clone(), root.clone(), {
+            let results = Rc::clone(&results);
+            move |this| {
+                vec![
+                    Html::div(
+                        Attributes::new(),
+                        Events::new()
+                            .on_input_parse::<usize, _>(this, record(&results))
+                            .on_contenteditable_input(this, record(&results)),
+                        vec![Html::text("1")],
+                    ),
+                    Html::textarea(
+                        Attributes::new(),
+                        Events::new()
+                            .on_checked(this, record(&results))
+                            .on_files(this, record(&results)),
+                        vec![],
+                    ),
+                    Html::div(
+                        Attributes::new().string("contenteditable", "true"),
+                        Events::new().on_contenteditable_input(this, record(&results)),
+                        vec![Html::text("text")],
+                    ),
+                ]
+            }
+        });
+        node.render();
+        let children = root.children();
+
+        backend.dispatch_event(&children[0], "input", true);
+        backend.dispatch_event(&children[1], "change", true);
+        backend.dispatch_event(&children[2], "input", true);
+
+        assert_eq!(
+            *results.borrow(),
+            vec![
+                r#"Err(UnsupportedElement("div"))"#,
+                r#"Err(UnsupportedElement("div"))"#,
+                r#"Err(UnsupportedElement("textarea"))"#,
+                r#"Err(UnsupportedElement("textarea"))"#,
+                r#"Ok("text")"#,
+            ]
+        );
+    }
+}
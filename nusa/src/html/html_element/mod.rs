@@ -1,11 +1,20 @@
 use super::Html;
 use crate::v_node::v_element::{VAttributes, VEvents};
 
+pub mod aria;
 pub mod attributes;
 pub mod events;
+pub mod form_value;
+pub mod svg;
 
+pub use aria::{
+    AriaAutocomplete, AriaCurrent, AriaHasPopup, AriaInvalid, AriaLive, AriaOrientation, AriaRole,
+    AriaSort, AriaTristate,
+};
 pub use attributes::Attributes;
 pub use events::Events;
+pub use form_value::FormValueError;
+pub use svg::{PathData, Transform};
 
 pub struct HtmlElement {
     pub tag_name: String,
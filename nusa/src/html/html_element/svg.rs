@@ -0,0 +1,160 @@
+//! Values of SVG attributes, which are given to `Attributes::d` and `Attributes::transform`.
+
+/// Commands of `d`.
+///
+/// ```ignore
+/// PathData::new().move_to(0.0, 0.0).line_to(10.0, 0.0).line_to(10.0, 10.0).close()
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct PathData {
+    commands: Vec<String>,
+}
+
+/// Functions of `transform`, which are applied from right to left.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Transform {
+    functions: Vec<String>,
+}
+
+macro_rules! path_command {
+    ($command:tt as $f_name:ident($($arg:ident),*)) => {
+        pub fn $f_name(self, $($arg: f64),*) -> Self {
+            self.push($command, &[$($arg),*])
+        }
+    };
+}
+
+impl PathData {
+    pub fn new() -> Self {
+        Self { commands: vec![] }
+    }
+
+    fn push(mut self, command: &str, args: &[f64]) -> Self {
+        let args = args.iter().map(f64::to_string).collect::<Vec<_>>();
+        self.commands.push(format!("{}{}", command, args.join(" ")));
+        self
+    }
+
+    path_command!("M" as move_to(x, y));
+    path_command!("m" as move_by(dx, dy));
+    path_command!("L" as line_to(x, y));
+    path_command!("l" as line_by(dx, dy));
+    path_command!("H" as horizontal_to(x));
+    path_command!("h" as horizontal_by(dx));
+    path_command!("V" as vertical_to(y));
+    path_command!("v" as vertical_by(dy));
+    path_command!("C" as cubic_to(x1, y1, x2, y2, x, y));
+    path_command!("c" as cubic_by(dx1, dy1, dx2, dy2, dx, dy));
+    path_command!("S" as smooth_cubic_to(x2, y2, x, y));
+    path_command!("s" as smooth_cubic_by(dx2, dy2, dx, dy));
+    path_command!("Q" as quadratic_to(x1, y1, x, y));
+    path_command!("q" as quadratic_by(dx1, dy1, dx, dy));
+    path_command!("T" as smooth_quadratic_to(x, y));
+    path_command!("t" as smooth_quadratic_by(dx, dy));
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_to(
+        self,
+        rx: f64,
+        ry: f64,
+        x_axis_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        x: f64,
+        y: f64,
+    ) -> Self {
+        self.push(
+            "A",
+            &Self::arc_args(rx, ry, x_axis_rotation, large_arc, sweep, x, y),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_by(
+        self,
+        rx: f64,
+        ry: f64,
+        x_axis_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        dx: f64,
+        dy: f64,
+    ) -> Self {
+        self.push(
+            "a",
+            &Self::arc_args(rx, ry, x_axis_rotation, large_arc, sweep, dx, dy),
+        )
+    }
+
+    pub fn close(self) -> Self {
+        self.push("Z", &[])
+    }
+
+    fn arc_args(
+        rx: f64,
+        ry: f64,
+        x_axis_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        x: f64,
+        y: f64,
+    ) -> [f64; 7] {
+        let flag = |flag: bool| if flag { 1.0 } else { 0.0 };
+        [rx, ry, x_axis_rotation, flag(large_arc), flag(sweep), x, y]
+    }
+}
+
+impl std::fmt::Display for PathData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.commands.join(" "))
+    }
+}
+
+impl Transform {
+    pub fn new() -> Self {
+        Self { functions: vec![] }
+    }
+
+    fn push(mut self, function: &str, args: &[f64]) -> Self {
+        let args = args.iter().map(f64::to_string).collect::<Vec<_>>();
+        self.functions
+            .push(format!("{}({})", function, args.join(" ")));
+        self
+    }
+
+    pub fn translate(self, x: f64, y: f64) -> Self {
+        self.push("translate", &[x, y])
+    }
+
+    pub fn scale(self, x: f64, y: f64) -> Self {
+        self.push("scale", &[x, y])
+    }
+
+    /// Rotates by `angle` degrees around the origin.
+    pub fn rotate(self, angle: f64) -> Self {
+        self.push("rotate", &[angle])
+    }
+
+    /// Rotates by `angle` degrees around `(cx, cy)`.
+    pub fn rotate_around(self, angle: f64, cx: f64, cy: f64) -> Self {
+        self.push("rotate", &[angle, cx, cy])
+    }
+
+    pub fn skew_x(self, angle: f64) -> Self {
+        self.push("skewX", &[angle])
+    }
+
+    pub fn skew_y(self, angle: f64) -> Self {
+        self.push("skewY", &[angle])
+    }
+
+    pub fn matrix(self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        self.push("matrix", &[a, b, c, d, e, f])
+    }
+}
+
+impl std::fmt::Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.functions.join(" "))
+    }
+}
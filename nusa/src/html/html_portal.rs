@@ -0,0 +1,48 @@
+use super::Html;
+use std::any::Any;
+use std::rc::Rc;
+
+pub struct HtmlPortal {
+    pub target: PortalTarget,
+    pub children: Vec<Html>,
+}
+
+/// A node which children of a portal are rendered into.
+/// It is a `web_sys::Node`, or a node of a `DomBackend` like `MemoryNode`.
+#[derive(Clone)]
+pub struct PortalTarget {
+    node: Rc<dyn Any>,
+    is_same: fn(&dyn Any, &dyn Any) -> bool,
+}
+
+impl PortalTarget {
+    pub fn new<N: Clone + PartialEq + 'static>(node: N) -> Self {
+        Self {
+            node: Rc::new(node),
+            is_same: Self::is_same_as::<N>,
+        }
+    }
+
+    /// Returns the node if it is given as `N`.
+    pub fn get<N: Clone + 'static>(&self) -> Option<N> {
+        self.node.downcast_ref::<N>().cloned()
+    }
+
+    /// Whether both are the same node, which keeps the rendered portal.
+    pub fn is_same(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.node, &other.node) || (self.is_same)(&*self.node, &*other.node)
+    }
+
+    fn is_same_as<N: PartialEq + 'static>(node: &dyn Any, other: &dyn Any) -> bool {
+        match (node.downcast_ref::<N>(), other.downcast_ref::<N>()) {
+            (Some(node), Some(other)) => node == other,
+            _ => false,
+        }
+    }
+}
+
+impl From<web_sys::Node> for PortalTarget {
+    fn from(node: web_sys::Node) -> Self {
+        Self::new(node)
+    }
+}
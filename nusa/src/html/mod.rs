@@ -1,9 +1,12 @@
 use crate::HtmlPrefab;
 
 pub mod html_element;
+pub mod html_portal;
 pub mod html_text;
+pub mod namespace;
 
 pub use html_element::HtmlElement;
+pub use html_portal::{HtmlPortal, PortalTarget};
 pub use html_text::HtmlText;
 
 pub enum Html {
@@ -12,6 +15,7 @@ pub enum Html {
     HtmlText(HtmlText),
     Fragment(Vec<Html>),
     RNode(web_sys::Node),
+    Portal(HtmlPortal),
     None,
 }
 
@@ -66,6 +70,18 @@ impl Html {
     pub fn node(node: web_sys::Node) -> Self {
         Self::RNode(node)
     }
+
+    /// Renders `children` into `target` instead of the parent, e.g. `document.body` for a modal.
+    /// Children stay in the component tree, so messages and `Sub` work as if they are in the parent.
+    /// Events bubble in `target` and do not reach elements of the parent.
+    pub fn portal(target: web_sys::Node, children: Vec<Self>) -> Self {
+        Self::portal_to(PortalTarget::from(target), children)
+    }
+
+    /// `Html::portal` with a node of any `DomBackend`, e.g. a `MemoryNode` in tests.
+    pub fn portal_to(target: PortalTarget, children: Vec<Self>) -> Self {
+        Self::Portal(HtmlPortal { target, children })
+    }
 }
 
 macro_rules! element {
@@ -192,6 +208,119 @@ impl Html {
     element!("wbr" as wbr, wbr_ns);
 }
 
+macro_rules! svg_element {
+    ($tag_name:tt as $f_name:ident) => {
+        pub fn $f_name(
+            attrs: html_element::Attributes,
+            events: html_element::Events,
+            children: Vec<Self>,
+        ) -> Self {
+            Self::element_ns($tag_name, "svg", attrs, events, children)
+        }
+    };
+}
+
+/// SVG elements. They are created in the SVG namespace without `xmlns`.
+/// Children of `svg` are created in the SVG namespace even by `Html::element`, except in `foreignObject`.
+impl Html {
+    svg_element!("a" as svg_a);
+    svg_element!("animate" as svg_animate);
+    svg_element!("animateMotion" as svg_animate_motion);
+    svg_element!("animateTransform" as svg_animate_transform);
+    svg_element!("circle" as svg_circle);
+    svg_element!("clipPath" as svg_clip_path);
+    svg_element!("defs" as svg_defs);
+    svg_element!("desc" as svg_desc);
+    svg_element!("ellipse" as svg_ellipse);
+    svg_element!("feBlend" as svg_fe_blend);
+    svg_element!("feColorMatrix" as svg_fe_color_matrix);
+    svg_element!("feComposite" as svg_fe_composite);
+    svg_element!("feDropShadow" as svg_fe_drop_shadow);
+    svg_element!("feFlood" as svg_fe_flood);
+    svg_element!("feGaussianBlur" as svg_fe_gaussian_blur);
+    svg_element!("feMerge" as svg_fe_merge);
+    svg_element!("feMergeNode" as svg_fe_merge_node);
+    svg_element!("feOffset" as svg_fe_offset);
+    svg_element!("filter" as svg_filter);
+    svg_element!("foreignObject" as svg_foreign_object);
+    svg_element!("g" as svg_g);
+    svg_element!("image" as svg_image);
+    svg_element!("line" as svg_line);
+    svg_element!("linearGradient" as svg_linear_gradient);
+    svg_element!("marker" as svg_marker);
+    svg_element!("mask" as svg_mask);
+    svg_element!("metadata" as svg_metadata);
+    svg_element!("mpath" as svg_mpath);
+    svg_element!("path" as svg_path);
+    svg_element!("pattern" as svg_pattern);
+    svg_element!("polygon" as svg_polygon);
+    svg_element!("polyline" as svg_polyline);
+    svg_element!("radialGradient" as svg_radial_gradient);
+    svg_element!("rect" as svg_rect);
+    svg_element!("script" as svg_script);
+    svg_element!("set" as svg_set);
+    svg_element!("stop" as svg_stop);
+    svg_element!("style" as svg_style);
+    svg_element!("svg" as svg);
+    svg_element!("switch" as svg_switch);
+    svg_element!("symbol" as svg_symbol);
+    svg_element!("text" as svg_text);
+    svg_element!("textPath" as svg_text_path);
+    svg_element!("title" as svg_title);
+    svg_element!("tspan" as svg_tspan);
+    svg_element!("use" as svg_use);
+    svg_element!("view" as svg_view);
+}
+
+macro_rules! mathml_element {
+    ($tag_name:tt as $f_name:ident) => {
+        pub fn $f_name(
+            attrs: html_element::Attributes,
+            events: html_element::Events,
+            children: Vec<Self>,
+        ) -> Self {
+            Self::element_ns($tag_name, "math", attrs, events, children)
+        }
+    };
+}
+
+/// MathML elements. They are created in the MathML namespace without `xmlns`.
+/// Children of `math` are created in the MathML namespace even by `Html::element`.
+impl Html {
+    mathml_element!("annotation" as annotation);
+    mathml_element!("annotation-xml" as annotation_xml);
+    mathml_element!("maction" as maction);
+    mathml_element!("math" as math);
+    mathml_element!("menclose" as menclose);
+    mathml_element!("merror" as merror);
+    mathml_element!("mfenced" as mfenced);
+    mathml_element!("mfrac" as mfrac);
+    mathml_element!("mi" as mi);
+    mathml_element!("mmultiscripts" as mmultiscripts);
+    mathml_element!("mn" as mn);
+    mathml_element!("mo" as mo);
+    mathml_element!("mover" as mover);
+    mathml_element!("mpadded" as mpadded);
+    mathml_element!("mphantom" as mphantom);
+    mathml_element!("mprescripts" as mprescripts);
+    mathml_element!("mroot" as mroot);
+    mathml_element!("mrow" as mrow);
+    mathml_element!("ms" as ms);
+    mathml_element!("mspace" as mspace);
+    mathml_element!("msqrt" as msqrt);
+    mathml_element!("mstyle" as mstyle);
+    mathml_element!("msub" as msub);
+    mathml_element!("msubsup" as msubsup);
+    mathml_element!("msup" as msup);
+    mathml_element!("mtable" as mtable);
+    mathml_element!("mtd" as mtd);
+    mathml_element!("mtext" as mtext);
+    mathml_element!("mtr" as mtr);
+    mathml_element!("munder" as munder);
+    mathml_element!("munderover" as munderover);
+    mathml_element!("semantics" as semantics);
+}
+
 impl std::default::Default for Html {
     fn default() -> Self {
         Self::none()
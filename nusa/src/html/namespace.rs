@@ -0,0 +1,21 @@
+//! Namespaces which can be given to `element_ns` by name without declaring `xmlns:*`.
+
+pub const HTML: &str = "http://www.w3.org/1999/xhtml";
+pub const MATHML: &str = "http://www.w3.org/1998/Math/MathML";
+pub const SVG: &str = "http://www.w3.org/2000/svg";
+pub const XLINK: &str = "http://www.w3.org/1999/xlink";
+pub const XML: &str = "http://www.w3.org/XML/1998/namespace";
+pub const XMLNS: &str = "http://www.w3.org/2000/xmlns/";
+
+/// The namespace of a built-in name like `svg` or `math`.
+pub fn built_in(name: &str) -> Option<&'static str> {
+    match name {
+        "html" => Some(HTML),
+        "math" => Some(MATHML),
+        "svg" => Some(SVG),
+        "xlink" => Some(XLINK),
+        "xml" => Some(XML),
+        "xmlns" => Some(XMLNS),
+        _ => None,
+    }
+}
@@ -1,5 +1,6 @@
 use crate::html_node::BasicHtmlNode;
 use crate::html_prefab::BasicHtmlPrefab;
+use crate::style::{Style, StyleHandle};
 use crate::{Html, HtmlNode};
 use kagura::component::{Constructor, Render, Update};
 use kagura::node::{BasicNodeMsg, Msg, SubHandler};
@@ -21,13 +22,21 @@ impl Sub {
 }
 
 pub trait HtmlComponent: Update + Render<Html> + Constructor + 'static {
+    /// The stylesheet of this component type. It is called once on the first mount.
+    fn stylesheet() -> Option<Style> {
+        None
+    }
+
     fn node_constructor(
         index_id: Option<String>,
         sub_handler: Option<SubHandler<Self>>,
         state: Pin<Box<Self>>,
         children: Self::Children,
     ) -> Box<dyn HtmlNode> {
-        Box::new(BasicHtmlNode::new(index_id, sub_handler, state, children))
+        Box::new(
+            BasicHtmlNode::new(index_id, sub_handler, state, children)
+                .with_style(StyleHandle::mount::<Self>(Self::stylesheet)),
+        )
     }
 
     fn new<Target: Component + 'static>(
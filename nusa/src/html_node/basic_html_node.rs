@@ -1,5 +1,6 @@
 use super::HtmlNode;
 use crate::html_prefab::BasicHtmlPrefab;
+use crate::style::StyleHandle;
 use crate::HtmlRenderer;
 use crate::{Html, HtmlPrefab, VNode};
 use kagura::component::{Render, Update};
@@ -13,6 +14,7 @@ pub struct BasicHtmlNode<This: Render<Html> + Update + 'static> {
     state: BasicComponentState<This>,
     html_renderer: HtmlRenderer<This>,
     index_id: Option<String>,
+    style: Option<StyleHandle>,
 }
 
 impl<This: Render<Html> + Update> BasicHtmlNode<This> {
@@ -28,8 +30,15 @@ impl<This: Render<Html> + Update> BasicHtmlNode<This> {
             state: BasicComponentState::new(state, sub_handler),
             html_renderer,
             index_id,
+            style: None,
         }
     }
+
+    /// Keeps the stylesheet of the component while this node is mounted.
+    pub fn with_style(mut self, style: Option<StyleHandle>) -> Self {
+        self.style = style;
+        self
+    }
 }
 
 impl<This: Render<Html> + Update> UpdateNode for BasicHtmlNode<This> {
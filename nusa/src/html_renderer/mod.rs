@@ -1,9 +1,12 @@
-use crate::v_node::{VElement, VText};
+use crate::html::{namespace, PortalTarget};
+use crate::v_node::v_element::VAttributes;
+use crate::v_node::{VElement, VPortal, VText};
 use crate::Html;
 use crate::HtmlNode;
 use crate::VNode;
 use kagura::component::Render;
 use kagura::node::{Msg, NodeCmd};
+use std::cell::Cell;
 use std::collections::VecDeque;
 use std::pin::Pin;
 use std::rc::Rc;
@@ -18,6 +21,7 @@ enum RenderedNode {
     Text,
     Fragment(VecDeque<RenderedNode>),
     Element(VecDeque<RenderedNode>),
+    Portal(PortalTarget, VecDeque<RenderedNode>),
     Component(Box<dyn HtmlNode>),
 }
 
@@ -53,7 +57,7 @@ impl<This: Render<Html>> HtmlRenderer<This> {
                 }
                 scedules
             }
-            RenderedNode::Fragment(children) => {
+            RenderedNode::Fragment(children) | RenderedNode::Portal(_, children) => {
                 let mut scedules = NodeCmd::new(VecDeque::new());
                 for child in children {
                     let mut node_cmd = Self::update_rendered(child, msg.clone());
@@ -100,22 +104,62 @@ impl<This: Render<Html>> HtmlRenderer<This> {
 
                 (RenderedNode::Fragment(rendered.0), rendered.1, rendered.2)
             }
-            Html::HtmlElement(element) => {
+            Html::HtmlElement(mut element) => {
                 let rendered_nodes = if let RenderedNode::Element(rendered_nodes) = rendered_node {
                     rendered_nodes
                 } else {
                     VecDeque::new()
                 };
 
+                let prefixes = element
+                    .attributes
+                    .iter()
+                    .filter_map(|(attr_name, attr_values)| {
+                        attr_name
+                            .strip_prefix("xmlns:")
+                            .map(|prefix| (String::from(prefix), attr_values.to_string()))
+                    })
+                    .collect::<Vec<_>>();
+                let is_new_namespace_scope = !prefixes.is_empty();
+                if is_new_namespace_scope {
+                    namespace_context.push_scope();
+                    for (prefix, namespace) in prefixes {
+                        namespace_context.insert_ns(prefix, namespace);
+                    }
+                }
+
                 let (default_namespace, is_new_default_namespace_scope) =
                     if let Some(default_namespace) = element.attributes.get("xmlns") {
                         let default_namespace = default_namespace.to_string();
-                        namespace_context.push_default_ns(default_namespace.clone());
+                        namespace_context.push_default_ns(Some(default_namespace.clone()));
                         (Some(default_namespace), true)
                     } else {
                         (namespace_context.default_ns().map(String::clone), false)
                     };
 
+                // The element and its attributes can use prefixes which are declared by itself.
+                let namespace = if let Some(namespace_name) = element.namespace_name {
+                    namespace_context
+                        .get_ns(namespace_name.as_str())
+                        .map(String::from)
+                } else {
+                    default_namespace
+                };
+                Self::resolve_attribute_namespaces(&mut element.attributes, namespace_context);
+
+                // Like the HTML parser, `svg` and `math` give their namespace to children,
+                // and children of `foreignObject` are HTML.
+                let is_new_default_namespace_scope = if is_new_default_namespace_scope {
+                    true
+                } else if let Some(children_namespace) =
+                    Self::children_namespace(&element.tag_name, namespace.as_deref())
+                {
+                    namespace_context.push_default_ns(children_namespace.map(String::from));
+                    true
+                } else {
+                    false
+                };
+
                 let children = Self::render_html_group(
                     rendered_nodes,
                     element.children.into(),
@@ -126,13 +170,9 @@ impl<This: Render<Html>> HtmlRenderer<This> {
                     namespace_context.pop_default_ns();
                 }
 
-                let namespace = if let Some(namespace_name) = element.namespace_name {
-                    namespace_context
-                        .get_ns(namespace_name.as_str())
-                        .map(String::clone)
-                } else {
-                    default_namespace
-                };
+                if is_new_namespace_scope {
+                    namespace_context.pop_scope();
+                }
 
                 (
                     RenderedNode::Element(children.0),
@@ -143,6 +183,7 @@ impl<This: Render<Html>> HtmlRenderer<This> {
                         events: element.events,
                         children: children.1,
                         index_id: element.index_id,
+                        property_defaults: Default::default(),
                     })]
                     .into(),
                     children.2,
@@ -176,6 +217,32 @@ impl<This: Render<Html>> HtmlRenderer<This> {
                 vec![VNode::RNode(r_node)].into(),
                 NodeCmd::new(VecDeque::new()),
             ),
+            Html::Portal(portal) => {
+                let rendered_nodes = if let RenderedNode::Portal(_, rendered_nodes) = rendered_node
+                {
+                    rendered_nodes
+                } else {
+                    VecDeque::new()
+                };
+
+                // The target is outside of the parent, so namespaces of the parent are not inherited.
+                let children = Self::render_html_group(
+                    rendered_nodes,
+                    portal.children.into(),
+                    &mut NamespaceContext::new(),
+                );
+
+                (
+                    RenderedNode::Portal(portal.target.clone(), children.0),
+                    vec![VNode::VPortal(VPortal {
+                        target: portal.target,
+                        children: children.1,
+                        id: Rc::new(Cell::new(None)),
+                    })]
+                    .into(),
+                    children.2,
+                )
+            }
             Html::None => (
                 RenderedNode::None,
                 VecDeque::new(),
@@ -184,6 +251,36 @@ impl<This: Render<Html>> HtmlRenderer<This> {
         }
     }
 
+    /// The default namespace of children if an element changes it without `xmlns`.
+    fn children_namespace(tag_name: &str, namespace: Option<&str>) -> Option<Option<&'static str>> {
+        match (tag_name, namespace) {
+            ("svg", Some(namespace::SVG)) => Some(Some(namespace::SVG)),
+            ("math", Some(namespace::MATHML)) => Some(Some(namespace::MATHML)),
+            ("foreignObject", Some(namespace::SVG)) => Some(None),
+            _ => None,
+        }
+    }
+
+    /// Sets namespaces of prefixed attributes like `xlink:href`.
+    fn resolve_attribute_namespaces(
+        attributes: &mut VAttributes,
+        namespace_context: &NamespaceContext,
+    ) {
+        let attr_names = attributes
+            .iter()
+            .filter(|(attr_name, _)| attr_name.contains(':'))
+            .map(|(attr_name, _)| attr_name.clone())
+            .collect::<Vec<_>>();
+
+        for attr_name in attr_names {
+            if let (Some((prefix, _)), Some(attr_values)) =
+                (attr_name.split_once(':'), attributes.get_mut(&attr_name))
+            {
+                attr_values.namespace = namespace_context.get_ns(prefix).map(String::from);
+            }
+        }
+    }
+
     fn render_html_group(
         prev_rendered_nodes: VecDeque<RenderedNode>,
         htmls: VecDeque<Html>,
@@ -243,6 +340,10 @@ impl<This: Render<Html>> HtmlRenderer<This> {
                 Html::HtmlElement(..) => true,
                 _ => false,
             },
+            RenderedNode::Portal(prev_target, ..) => match html {
+                Html::Portal(portal) => prev_target.is_same(&portal.target),
+                _ => false,
+            },
             RenderedNode::Fragment(..) => match html {
                 Html::Fragment(..) => true,
                 _ => false,
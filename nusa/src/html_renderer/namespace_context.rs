@@ -1,8 +1,9 @@
+use crate::html::namespace;
 use std::collections::{HashMap, VecDeque};
 
 pub struct NamespaceContext {
     named_ns: VecDeque<HashMap<String, String>>,
-    default_ns: VecDeque<String>,
+    default_ns: VecDeque<Option<String>>,
 }
 
 impl NamespaceContext {
@@ -13,34 +14,41 @@ impl NamespaceContext {
         }
     }
 
-    #[allow(dead_code)]
     pub fn push_scope(&mut self) {
         self.named_ns.push_back(HashMap::new());
     }
 
-    #[allow(dead_code)]
+    /// Declares `name` in the current scope, like `xmlns:name="ns"`.
+    pub fn insert_ns(&mut self, name: impl Into<String>, ns: impl Into<String>) {
+        if let Some(scope) = self.named_ns.back_mut() {
+            scope.insert(name.into(), ns.into());
+        }
+    }
+
     pub fn pop_scope(&mut self) -> Option<HashMap<String, String>> {
         self.named_ns.pop_back()
     }
 
-    pub fn push_default_ns(&mut self, ns: String) {
+    /// `None` resets the default namespace to HTML, like children of `foreignObject`.
+    pub fn push_default_ns(&mut self, ns: Option<String>) {
         self.default_ns.push_back(ns);
     }
 
     pub fn pop_default_ns(&mut self) -> Option<String> {
-        self.default_ns.pop_back()
+        self.default_ns.pop_back().flatten()
     }
 
-    pub fn get_ns(&self, name: &str) -> Option<&String> {
+    /// Returns the namespace of `name`. Built-in names like `svg` are used if it is not declared.
+    pub fn get_ns(&self, name: &str) -> Option<&str> {
         for ns in self.named_ns.iter().rev() {
             if let Some(ns) = ns.get(name) {
                 return Some(ns);
             }
         }
-        None
+        namespace::built_in(name)
     }
 
     pub fn default_ns(&self) -> Option<&String> {
-        self.default_ns.back()
+        self.default_ns.back().and_then(Option::as_ref)
     }
 }
@@ -5,24 +5,38 @@ extern crate wasm_bindgen;
 extern crate wasm_bindgen_futures;
 extern crate web_sys;
 
+pub mod dom_backend;
+pub mod dom_cmd;
 pub mod dom_events;
 pub mod dom_node;
+pub mod dom_ref;
 pub mod dom_renderer;
+pub mod focus;
+pub mod form;
 pub mod html;
 pub mod html_component;
 pub mod html_node;
 pub mod html_prefab;
 pub mod html_renderer;
+pub mod router;
+pub mod snapshot;
+pub mod string_node;
+pub mod string_renderer;
+pub mod style;
+pub mod testing;
 pub mod util;
 pub mod v_node;
 
+pub use dom_backend::DomBackend;
 pub use dom_events::DomEvents;
+pub use dom_ref::{DomRef, NodeRef};
 pub use dom_renderer::DomRenderer;
 pub use html::Html;
 pub use html_component::HtmlComponent;
 pub use html_node::HtmlNode;
 pub use html_prefab::HtmlPrefab;
 pub use html_renderer::HtmlRenderer;
+pub use string_renderer::StringRenderer;
 pub use v_node::VNode;
 
 pub mod prelude {
@@ -0,0 +1,184 @@
+//! Access to `window.history` and `window.location`. These work only in browser.
+
+use super::{Location, RouterMode};
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Listens to `popstate` or `hashchange` of `window` while it is alive.
+pub struct LocationListener {
+    state: Rc<RefCell<ListenerState>>,
+    event_type: &'static str,
+    listener: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+/// Resolved on the next change of the location.
+pub struct LocationChange {
+    state: Rc<RefCell<ListenerState>>,
+}
+
+struct ListenerState {
+    is_changed: bool,
+    waker: Option<Waker>,
+}
+
+/// The current location of the page.
+pub fn location(mode: RouterMode) -> Location {
+    let location = web_sys::window().map(|window| window.location());
+    match (mode, location) {
+        (RouterMode::History, Some(location)) => {
+            let pathname = location.pathname().unwrap_or_default();
+            let search = location.search().unwrap_or_default();
+            mode.location(&format!("{}{}", pathname, search))
+        }
+        (RouterMode::Hash, Some(location)) => mode.location(&location.hash().unwrap_or_default()),
+        (_, None) => Location::new(),
+    }
+}
+
+/// Adds a history entry of `href` with a new entry key, and notifies routers.
+pub fn push(mode: RouterMode, href: &str) {
+    if let Some(history) = history() {
+        let key = JsValue::from_str(&new_entry_key());
+        let _ = history.push_state_with_url(&key, "", Some(href));
+        notify(mode);
+    }
+}
+
+/// Replaces the current history entry with `href`, and notifies routers.
+/// The entry key is kept.
+pub fn replace(mode: RouterMode, href: &str) {
+    if let Some(history) = history() {
+        let key = history.state().unwrap_or(JsValue::NULL);
+        let _ = history.replace_state_with_url(&key, "", Some(href));
+        notify(mode);
+    }
+}
+
+pub fn back() {
+    if let Some(history) = history() {
+        let _ = history.back();
+    }
+}
+
+pub fn forward() {
+    if let Some(history) = history() {
+        let _ = history.forward();
+    }
+}
+
+/// A key which identifies the current history entry. Entries added by `push` have it.
+pub fn entry_key() -> Option<String> {
+    history().and_then(|history| history.state().ok()?.as_string())
+}
+
+/// Gives a key to the current history entry if it has none,
+/// and stops the browser from restoring scroll positions by itself.
+pub fn init_scroll_restoration() {
+    if let Some(history) = history() {
+        if entry_key().is_none() {
+            let key = JsValue::from_str(&new_entry_key());
+            let _ = history.replace_state(&key, "");
+        }
+        let _ = history.set_scroll_restoration(web_sys::ScrollRestoration::Manual);
+    }
+}
+
+/// The scroll position of the page.
+pub fn scroll_position() -> (f64, f64) {
+    web_sys::window()
+        .map(|window| {
+            (
+                window.scroll_x().unwrap_or_default(),
+                window.scroll_y().unwrap_or_default(),
+            )
+        })
+        .unwrap_or_default()
+}
+
+fn new_entry_key() -> String {
+    format!(
+        "{:x}-{:x}",
+        js_sys::Date::now() as u64,
+        (js_sys::Math::random() * u32::MAX as f64) as u32
+    )
+}
+
+fn history() -> Option<web_sys::History> {
+    web_sys::window().and_then(|window| window.history().ok())
+}
+
+/// `pushState` and `replaceState` dispatch no event, so it is dispatched here.
+fn notify(mode: RouterMode) {
+    if let (Some(window), Ok(event)) = (web_sys::window(), web_sys::Event::new(mode.event_type())) {
+        let _ = window.dispatch_event(&event);
+    }
+}
+
+impl LocationListener {
+    pub fn new(mode: RouterMode) -> Self {
+        let state = Rc::new(RefCell::new(ListenerState {
+            is_changed: false,
+            waker: None,
+        }));
+        let event_type = mode.event_type();
+
+        let listener = Closure::wrap(Box::new({
+            let state = Rc::clone(&state);
+            move |_: web_sys::Event| {
+                let mut state = state.borrow_mut();
+                state.is_changed = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            }
+        }) as Box<dyn FnMut(web_sys::Event)>);
+
+        if let Some(window) = web_sys::window() {
+            let _ = window
+                .add_event_listener_with_callback(event_type, listener.as_ref().unchecked_ref());
+        }
+
+        Self {
+            state,
+            event_type,
+            listener,
+        }
+    }
+
+    pub fn changed(&self) -> LocationChange {
+        LocationChange {
+            state: Rc::clone(&self.state),
+        }
+    }
+}
+
+impl std::ops::Drop for LocationListener {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            let _ = window.remove_event_listener_with_callback(
+                self.event_type,
+                self.listener.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+impl Future for LocationChange {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        if state.is_changed {
+            state.is_changed = false;
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
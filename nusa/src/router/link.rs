@@ -0,0 +1,73 @@
+use super::Route;
+use crate::html::html_element::{Attributes, Events};
+use crate::{Html, HtmlComponent};
+use kagura::component::{Cmd, Constructor, Render, Update};
+use kagura::Component;
+use std::pin::Pin;
+
+pub struct Props<R> {
+    pub route: R,
+}
+
+pub enum Msg {
+    Navigate,
+    Ignore,
+}
+
+pub enum On {}
+
+/// An `a` element of a route. A click navigates with `pushState` instead of loading a page.
+/// Clicks with modifier keys are left to the browser, like opening a new tab.
+pub struct Link<R: Route> {
+    // boxed to mutate it through `Pin` even if `R` is `!Unpin`.
+    route: Box<R>,
+}
+
+impl<R: Route> Component for Link<R> {
+    type Props = Props<R>;
+    type Msg = Msg;
+    type Event = On;
+}
+
+impl<R: Route> Constructor for Link<R> {
+    fn constructor(props: Self::Props) -> Self {
+        Self {
+            route: Box::new(props.route),
+        }
+    }
+}
+
+impl<R: Route> Update for Link<R> {
+    fn on_load(mut self: Pin<&mut Self>, props: Self::Props) -> Cmd<Self> {
+        *self.route = props.route;
+        Cmd::none()
+    }
+
+    fn update(self: Pin<&mut Self>, msg: Self::Msg) -> Cmd<Self> {
+        match msg {
+            Msg::Navigate => super::push(self.route.as_ref()),
+            Msg::Ignore => Cmd::none(),
+        }
+    }
+}
+
+impl<R: Route> Render<Html> for Link<R> {
+    type Children = (Attributes, Vec<Html>);
+    fn render(&self, (attrs, children): Self::Children) -> Html {
+        Html::a(
+            attrs.href(super::href(self.route.as_ref())),
+            Events::new().on_click(self, |e| {
+                let has_modifier = e.ctrl_key() || e.meta_key() || e.shift_key() || e.alt_key();
+                if e.button() == 0 && !has_modifier && !e.default_prevented() {
+                    e.prevent_default();
+                    Msg::Navigate
+                } else {
+                    Msg::Ignore
+                }
+            }),
+            children,
+        )
+    }
+}
+
+impl<R: Route> HtmlComponent for Link<R> {}
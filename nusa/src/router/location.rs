@@ -0,0 +1,145 @@
+/// A path and a query of URL, which a route is parsed from and formatted to.
+///
+/// ```ignore
+/// let location = Location::parse("/users/42?tab=posts");
+/// assert_eq!(location.segments(), vec!["users", "42"]);
+/// assert_eq!(location.query_value("tab"), Some("posts"));
+/// assert_eq!(location.to_string(), "/users/42?tab=posts");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Location {
+    segments: Vec<String>,
+    query: Vec<(String, String)>,
+}
+
+impl Location {
+    pub fn new() -> Self {
+        Self {
+            segments: vec![],
+            query: vec![],
+        }
+    }
+
+    /// Parses `/path?query`. A fragment is ignored, and segments and values are percent-decoded.
+    pub fn parse(url: &str) -> Self {
+        let url = url.split('#').next().unwrap_or("");
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| decode(segment, false))
+            .collect();
+
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode(name, true), decode(value, true))
+            })
+            .collect();
+
+        Self { segments, query }
+    }
+
+    pub fn segment(mut self, segment: impl Into<String>) -> Self {
+        self.segments.push(segment.into());
+        self
+    }
+
+    pub fn query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn segments(&self) -> Vec<&str> {
+        self.segments.iter().map(String::as_str).collect()
+    }
+
+    /// The first value of `name` in the query.
+    pub fn query_value(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn query_values(&self, name: &str) -> Vec<&str> {
+        self.query
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "/")?;
+
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| encode(segment, "!$&'()*+,;=:@"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", segments.join("/"))?;
+
+        if !self.query.is_empty() {
+            let query = self
+                .query
+                .iter()
+                .map(|(name, value)| {
+                    format!(
+                        "{}={}",
+                        encode(name, "!$'()*,;:@/?"),
+                        encode(value, "!$'()*,;:@/?")
+                    )
+                })
+                .collect::<Vec<_>>();
+            write!(f, "?{}", query.join("&"))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn encode(text: &str, reserved: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        let c = byte as char;
+        if c.is_ascii_alphanumeric() || "-._~".contains(c) || (c.is_ascii() && reserved.contains(c))
+        {
+            encoded.push(c);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Decodes `%XX`. In a query, `+` is decoded as a space.
+fn decode(text: &str, is_query: bool) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len()
+                && bytes[i + 1].is_ascii_hexdigit()
+                && bytes[i + 2].is_ascii_hexdigit() =>
+            {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("0");
+                decoded.push(u8::from_str_radix(hex, 16).unwrap_or(0));
+                i += 3;
+                continue;
+            }
+            b'+' if is_query => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
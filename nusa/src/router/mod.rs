@@ -0,0 +1,347 @@
+//! Client-side routing with typed routes.
+//!
+//! ```ignore
+//! enum Page {
+//!     Home,
+//!     User { id: u64, tab: Option<String> },
+//! }
+//!
+//! impl Route for Page {
+//!     fn from_location(location: &Location) -> Option<Self> {
+//!         match location.segments().as_slice() {
+//!             [] => Some(Self::Home),
+//!             ["users", id] => Some(Self::User {
+//!                 id: id.parse().ok()?,
+//!                 tab: location.query_value("tab").map(String::from),
+//!             }),
+//!             _ => None,
+//!         }
+//!     }
+//!
+//!     fn to_location(&self) -> Location {
+//!         match self {
+//!             Self::Home => Location::new(),
+//!             Self::User { id, tab } => {
+//!                 let location = Location::new().segment("users").segment(id.to_string());
+//!                 match tab {
+//!                     Some(tab) => location.query("tab", tab),
+//!                     None => location,
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! // in render
+//! Router::<Page>::new(
+//!     self,
+//!     None,
+//!     router::Props {
+//!         mode: RouterMode::History,
+//!         restore_scroll: true,
+//!     },
+//!     Sub::map(|e| match e {
+//!         router::On::Change(page) => Msg::SetPage(page),
+//!     }),
+//!     vec![
+//!         Link::new(
+//!             self,
+//!             None,
+//!             link::Props { route: Page::Home },
+//!             Sub::none(),
+//!             (Attributes::new(), vec![Html::text("home")]),
+//!         ),
+//!         self.render_page(),
+//!     ],
+//! )
+//!
+//! // in update
+//! Msg::Save => router::push(&Page::Home),
+//! ```
+
+use crate::{Html, HtmlComponent};
+use kagura::component::{BatchProcess, Cmd, Constructor, Render, Update};
+use kagura::Component;
+use std::cell::Cell;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+pub mod history;
+pub mod link;
+pub mod location;
+pub mod scroll;
+
+pub use link::Link;
+pub use location::Location;
+
+thread_local! {
+    static MODE: Cell<RouterMode> = const { Cell::new(RouterMode::History) };
+}
+
+/// A typed route, which is parsed from and formatted to `Location`.
+pub trait Route: Sized + 'static {
+    /// Returns `None` if no route matches.
+    fn from_location(location: &Location) -> Option<Self>;
+    fn to_location(&self) -> Location;
+}
+
+/// How a route is stored in URL.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RouterMode {
+    /// `/path?query` with `history.pushState`.
+    History,
+    /// `#/path?query`, which works without server-side fallback.
+    Hash,
+}
+
+pub struct Props {
+    pub mode: RouterMode,
+    /// Restores the scroll position of the page when a history entry is visited again,
+    /// and scrolls to the top on a new entry.
+    pub restore_scroll: bool,
+}
+
+pub enum Msg {
+    Changed,
+}
+
+pub enum On<R> {
+    /// The location is changed. It is also submitted on the first mount.
+    Change(Option<R>),
+}
+
+/// Notifies the current route on `popstate` or `hashchange`, and renders children as they are.
+/// `Link` and commands in this module use the mode of the last constructed router.
+pub struct Router<R: Route> {
+    mode: RouterMode,
+    restore_scroll: bool,
+    _route: PhantomData<R>,
+}
+
+struct LocationBatch {
+    listener: history::LocationListener,
+}
+
+impl RouterMode {
+    pub fn href(&self, location: &Location) -> String {
+        match self {
+            Self::History => location.to_string(),
+            Self::Hash => format!("#{}", location),
+        }
+    }
+
+    /// Parses `/path?query` in history mode, or `#/path?query` in hash mode.
+    pub fn location(&self, url: &str) -> Location {
+        match self {
+            Self::History => Location::parse(url),
+            Self::Hash => Location::parse(url.trim_start_matches('#')),
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        match self {
+            Self::History => "popstate",
+            Self::Hash => "hashchange",
+        }
+    }
+}
+
+/// The mode of the last constructed router.
+pub fn mode() -> RouterMode {
+    MODE.with(|mode| mode.get())
+}
+
+pub fn href(route: &impl Route) -> String {
+    mode().href(&route.to_location())
+}
+
+/// Navigates to `route` with a new history entry.
+pub fn push<C: Component + 'static>(route: &impl Route) -> Cmd<C> {
+    let mode = mode();
+    let href = mode.href(&route.to_location());
+    Cmd::task(async move {
+        history::push(mode, &href);
+        Cmd::none()
+    })
+}
+
+/// Navigates to `route` without a new history entry.
+pub fn replace<C: Component + 'static>(route: &impl Route) -> Cmd<C> {
+    let mode = mode();
+    let href = mode.href(&route.to_location());
+    Cmd::task(async move {
+        history::replace(mode, &href);
+        Cmd::none()
+    })
+}
+
+pub fn back<C: Component + 'static>() -> Cmd<C> {
+    Cmd::task(async {
+        history::back();
+        Cmd::none()
+    })
+}
+
+pub fn forward<C: Component + 'static>() -> Cmd<C> {
+    Cmd::task(async {
+        history::forward();
+        Cmd::none()
+    })
+}
+
+impl<R: Route> Router<R> {
+    fn change(&self) -> Cmd<Self> {
+        let location = history::location(self.mode);
+        let change = Cmd::submit(On::Change(R::from_location(&location)));
+        if self.restore_scroll {
+            Cmd::list(vec![change, scroll::restore()])
+        } else {
+            change
+        }
+    }
+}
+
+impl<R: Route> Component for Router<R> {
+    type Props = Props;
+    type Msg = Msg;
+    type Event = On<R>;
+}
+
+impl<R: Route> Constructor for Router<R> {
+    fn constructor(props: Self::Props) -> Self {
+        MODE.with(|mode| mode.set(props.mode));
+        Self {
+            mode: props.mode,
+            restore_scroll: props.restore_scroll,
+            _route: PhantomData,
+        }
+    }
+}
+
+impl<R: Route> Update for Router<R> {
+    fn on_assemble(self: Pin<&mut Self>) -> Cmd<Self> {
+        if self.restore_scroll {
+            scroll::init();
+        }
+
+        Cmd::list(vec![
+            self.change(),
+            Cmd::batch(LocationBatch {
+                listener: history::LocationListener::new(self.mode),
+            }),
+        ])
+    }
+
+    fn update(self: Pin<&mut Self>, msg: Self::Msg) -> Cmd<Self> {
+        match msg {
+            Msg::Changed => self.change(),
+        }
+    }
+}
+
+impl<R: Route> Render<Html> for Router<R> {
+    type Children = Vec<Html>;
+    fn render(&self, children: Self::Children) -> Html {
+        Html::fragment(children)
+    }
+}
+
+impl<R: Route> HtmlComponent for Router<R> {}
+
+impl<R: Route> BatchProcess<Router<R>> for LocationBatch {
+    fn poll(&mut self) -> Pin<Box<dyn Future<Output = Cmd<Router<R>>>>> {
+        let changed = self.listener.changed();
+        Box::pin(async move {
+            changed.await;
+            Cmd::chain(Msg::Changed)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Page {
+        Home,
+        User { id: u64, tab: Option<String> },
+        Search(String),
+    }
+
+    impl Route for Page {
+        fn from_location(location: &Location) -> Option<Self> {
+            match location.segments().as_slice() {
+                [] => Some(Self::Home),
+                ["users", id] => Some(Self::User {
+                    id: id.parse().ok()?,
+                    tab: location.query_value("tab").map(String::from),
+                }),
+                ["search"] => Some(Self::Search(String::from(
+                    location.query_value("q").unwrap_or(""),
+                ))),
+                _ => None,
+            }
+        }
+
+        fn to_location(&self) -> Location {
+            match self {
+                Self::Home => Location::new(),
+                Self::User { id, tab } => {
+                    let location = Location::new().segment("users").segment(id.to_string());
+                    match tab {
+                        Some(tab) => location.query("tab", tab),
+                        None => location,
+                    }
+                }
+                Self::Search(q) => Location::new().segment("search").query("q", q),
+            }
+        }
+    }
+
+    fn parse(mode: RouterMode, url: &str) -> Option<Page> {
+        Page::from_location(&mode.location(url))
+    }
+
+    #[test]
+    fn it_parses_routes() {
+        assert_eq!(parse(RouterMode::History, "/"), Some(Page::Home));
+        assert_eq!(
+            parse(RouterMode::History, "/users/42/?tab=posts#top"),
+            Some(Page::User {
+                id: 42,
+                tab: Some(String::from("posts"))
+            })
+        );
+        assert_eq!(
+            parse(RouterMode::Hash, "#/search?q=a+b%26c"),
+            Some(Page::Search(String::from("a b&c")))
+        );
+        assert_eq!(parse(RouterMode::Hash, ""), Some(Page::Home));
+        assert_eq!(parse(RouterMode::History, "/users/x"), None);
+        assert_eq!(parse(RouterMode::History, "/unknown"), None);
+    }
+
+    #[test]
+    fn it_formats_routes() {
+        let page = Page::User {
+            id: 1,
+            tab: Some(String::from("a/b c")),
+        };
+        assert_eq!(
+            RouterMode::History.href(&page.to_location()),
+            "/users/1?tab=a/b%20c"
+        );
+        assert_eq!(
+            RouterMode::Hash.href(&Page::Search(String::from("1+1=2")).to_location()),
+            "#/search?q=1%2B1%3D2"
+        );
+        assert_eq!(RouterMode::History.href(&Page::Home.to_location()), "/");
+
+        for page in [page, Page::Search(String::from("日本語 & more"))] {
+            let href = RouterMode::Hash.href(&page.to_location());
+            assert_eq!(parse(RouterMode::Hash, &href), Some(page));
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Scroll positions of history entries, which are restored when the entry is visited again.
+
+use super::history;
+use crate::dom_cmd;
+use kagura::component::Cmd;
+use kagura::Component;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    static POSITIONS: RefCell<ScrollPositions> = RefCell::new(ScrollPositions::new());
+}
+
+/// Positions of entries which have been left.
+pub struct ScrollPositions {
+    current: Option<String>,
+    positions: HashMap<String, (f64, f64)>,
+    is_entered: bool,
+}
+
+impl ScrollPositions {
+    pub fn new() -> Self {
+        Self {
+            current: None,
+            positions: HashMap::new(),
+            is_entered: false,
+        }
+    }
+
+    /// Saves `position` for the entry which is left, and returns a position to scroll to.
+    /// It returns `None` on the first entry or if the entry is not changed, like `replace`.
+    /// An entry which is visited first is scrolled to the top.
+    pub fn enter(&mut self, key: Option<String>, position: (f64, f64)) -> Option<(f64, f64)> {
+        if !self.is_entered {
+            self.is_entered = true;
+            self.current = key;
+            return None;
+        }
+
+        if key.is_some() && key == self.current {
+            return None;
+        }
+
+        if let Some(current) = self.current.take() {
+            self.positions.insert(current, position);
+        }
+
+        let next = key
+            .as_ref()
+            .and_then(|key| self.positions.get(key).copied())
+            .unwrap_or((0.0, 0.0));
+        self.current = key;
+        Some(next)
+    }
+}
+
+impl std::default::Default for ScrollPositions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(super) fn init() {
+    history::init_scroll_restoration();
+}
+
+/// Saves the position of the last entry, and restores the position of the current entry after it is rendered.
+pub(super) fn restore<C: Component + 'static>() -> Cmd<C> {
+    let key = history::entry_key();
+    let position = history::scroll_position();
+    match POSITIONS.with(|positions| positions.borrow_mut().enter(key, position)) {
+        Some((x, y)) => dom_cmd::scroll_window_to(x, y),
+        None => Cmd::none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: &str) -> Option<String> {
+        Some(String::from(key))
+    }
+
+    #[test]
+    fn it_restores_positions_of_entries() {
+        let mut positions = ScrollPositions::new();
+
+        assert_eq!(positions.enter(key("a"), (0.0, 0.0)), None);
+        assert_eq!(positions.enter(key("b"), (0.0, 120.0)), Some((0.0, 0.0)));
+        assert_eq!(positions.enter(key("b"), (0.0, 40.0)), None);
+        assert_eq!(positions.enter(key("a"), (0.0, 80.0)), Some((0.0, 120.0)));
+        assert_eq!(positions.enter(key("b"), (0.0, 10.0)), Some((0.0, 80.0)));
+        assert_eq!(positions.enter(None, (5.0, 20.0)), Some((0.0, 0.0)));
+        assert_eq!(positions.enter(key("b"), (0.0, 0.0)), Some((5.0, 20.0)));
+    }
+}
@@ -0,0 +1,389 @@
+//! Deterministic serialization of virtual-DOM to compare render output in tests.
+//! Attributes and event types are sorted by name.
+//!
+//! ```ignore
+//! let v_nodes = nusa::snapshot::v_nodes(vec![Html::element(
+//!     "h1",
+//!     Attributes::new().class("title"),
+//!     Events::new(),
+//!     vec![Html::text("Hello World")],
+//! )]);
+//! nusa::snapshot::assert_snapshot("hello_world", &nusa::snapshot::to_text(&v_nodes));
+//! ```
+
+use crate::dom_node::basic_dom_node::BasicDomComponent;
+use crate::util::json_string;
+use crate::v_node::v_element::VEvents;
+use crate::v_node::VElement;
+use crate::{Html, HtmlRenderer, VNode};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// Directory of snapshot files, relative to `CARGO_MANIFEST_DIR`.
+pub const SNAPSHOT_DIR: &str = "snapshots";
+
+/// If this environment variable is set, `assert_snapshot` overwrites snapshot files.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "NUSA_UPDATE_SNAPSHOTS";
+
+/// Renders htmls to virtual-DOM without real-DOM.
+pub fn v_nodes(htmls: Vec<Html>) -> VecDeque<VNode> {
+    let state = Box::pin(BasicDomComponent::new());
+    let mut html_renderer = HtmlRenderer::<BasicDomComponent>::new();
+    html_renderer.set_children(htmls);
+    html_renderer.render(&state).0
+}
+
+/// Serializes virtual-DOM to indented HTML-like text.
+///
+/// - `@namespace`, `@index_id`, `@on`, `@capture` and `@refer` are shown as attributes.
+/// - DOM properties are shown as `.name` without values.
+/// - Texts are quoted.
+pub fn to_text(v_nodes: &VecDeque<VNode>) -> String {
+    let mut text = String::new();
+    for v_node in v_nodes {
+        write_text(v_node, 0, &mut text);
+    }
+    text
+}
+
+/// Serializes virtual-DOM to indented JSON.
+pub fn to_json(v_nodes: &VecDeque<VNode>) -> String {
+    let mut json = String::new();
+    write_json_list(v_nodes, 0, &mut json);
+    json.push('\n');
+    json
+}
+
+/// Compares `actual` with `snapshots/<name>.snap`.
+/// The file is written only if `NUSA_UPDATE_SNAPSHOTS` is set, so a missing file fails.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let is_update = std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some();
+    compare_snapshot(name, actual, is_update);
+}
+
+fn compare_snapshot(name: &str, actual: &str, is_update: bool) {
+    let path = snapshot_path(name);
+
+    if is_update {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(err) => panic!(
+            "snapshot `{}` can not be read: {}: {}\nset {} to create it",
+            name,
+            path.display(),
+            err,
+            UPDATE_SNAPSHOTS_ENV
+        ),
+    };
+    if expected != actual {
+        panic!(
+            "snapshot `{}` does not match: {}\n{}\nset {} to update it",
+            name,
+            path.display(),
+            diff_lines(&expected, actual),
+            UPDATE_SNAPSHOTS_ENV
+        );
+    }
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    PathBuf::from(manifest_dir)
+        .join(SNAPSHOT_DIR)
+        .join(format!("{}.snap", name))
+}
+
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let mut diff = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => diff += &format!("  {}\n", e),
+            (e, a) => {
+                if let Some(e) = e {
+                    diff += &format!("- {}\n", e);
+                }
+                if let Some(a) = a {
+                    diff += &format!("+ {}\n", a);
+                }
+            }
+        }
+    }
+    diff
+}
+
+fn sorted_attributes(element: &VElement) -> Vec<(&String, String)> {
+    let mut attributes = element
+        .attributes
+        .iter()
+        .filter(|(_, values)| values.property().is_none())
+        .map(|(name, values)| (name, values.to_string()))
+        .collect::<Vec<_>>();
+    attributes.sort_by(|a, b| a.0.cmp(b.0));
+    attributes
+}
+
+/// Names of DOM properties. Values are not shown because they can not be inspected without browser.
+fn sorted_properties(element: &VElement) -> Vec<&String> {
+    let mut properties = element
+        .attributes
+        .iter()
+        .filter(|(_, values)| values.property().is_some())
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    properties.sort();
+    properties
+}
+
+/// Event types which have bubble handlers and capture handlers.
+fn sorted_event_types(events: &VEvents) -> (Vec<&String>, Vec<&String>) {
+    let mut bubbles = vec![];
+    let mut captures = vec![];
+    for (event_type, handlers) in &events.events {
+        if !handlers.bubbles.is_empty() {
+            bubbles.push(event_type);
+        }
+        if !handlers.captures.is_empty() {
+            captures.push(event_type);
+        }
+    }
+    bubbles.sort();
+    captures.sort();
+    (bubbles, captures)
+}
+
+fn write_text(v_node: &VNode, depth: usize, text: &mut String) {
+    let indent = "  ".repeat(depth);
+    match v_node {
+        VNode::VText(v_text) => {
+            *text += &format!("{}{}\n", indent, json_string(&v_text.text));
+        }
+        VNode::RNode(..) => {
+            *text += &format!("{}[RNode]\n", indent);
+        }
+        VNode::VPortal(portal) => {
+            *text += &format!("{}[Portal]\n", indent);
+            for child in &portal.children {
+                write_text(child, depth + 1, text);
+            }
+        }
+        VNode::VElement(element) => {
+            *text += &format!("{}<{}", indent, element.tag_name);
+
+            if let Some(namespace) = &element.namespace {
+                *text += &format!(" @namespace={}", json_string(namespace));
+            }
+            if let Some(index_id) = &element.index_id {
+                *text += &format!(" @index_id={}", json_string(index_id));
+            }
+            for (name, value) in sorted_attributes(element) {
+                *text += &format!(" {}={}", name, json_string(&value));
+            }
+            for name in sorted_properties(element) {
+                *text += &format!(" .{}", name);
+            }
+            let (bubbles, captures) = sorted_event_types(&element.events);
+            if !bubbles.is_empty() {
+                *text += &format!(" @on={}", json_string(&join(&bubbles)));
+            }
+            if !captures.is_empty() {
+                *text += &format!(" @capture={}", json_string(&join(&captures)));
+            }
+            if !element.events.refers.is_empty() {
+                *text += &format!(" @refer=\"{}\"", element.events.refers.len());
+            }
+
+            if element.children.is_empty() {
+                *text += " />\n";
+            } else {
+                *text += ">\n";
+                for child in &element.children {
+                    write_text(child, depth + 1, text);
+                }
+                *text += &format!("{}</{}>\n", indent, element.tag_name);
+            }
+        }
+    }
+}
+
+fn write_json_list(v_nodes: &VecDeque<VNode>, depth: usize, json: &mut String) {
+    if v_nodes.is_empty() {
+        *json += "[]";
+        return;
+    }
+
+    let indent = "  ".repeat(depth);
+    *json += "[\n";
+    for (i, v_node) in v_nodes.iter().enumerate() {
+        *json += &format!("{}  ", indent);
+        write_json(v_node, depth + 1, json);
+        if i + 1 < v_nodes.len() {
+            json.push(',');
+        }
+        json.push('\n');
+    }
+    *json += &format!("{}]", indent);
+}
+
+fn write_json(v_node: &VNode, depth: usize, json: &mut String) {
+    let indent = "  ".repeat(depth);
+    match v_node {
+        VNode::VText(v_text) => {
+            *json += &format!(
+                "{{\"type\": \"text\", \"text\": {}}}",
+                json_string(&v_text.text)
+            );
+        }
+        VNode::RNode(..) => {
+            *json += "{\"type\": \"r_node\"}";
+        }
+        VNode::VPortal(portal) => {
+            *json += "{\n";
+            *json += &format!("{}  \"type\": \"portal\",\n", indent);
+            *json += &format!("{}  \"children\": ", indent);
+            write_json_list(&portal.children, depth + 1, json);
+            *json += &format!("\n{}}}", indent);
+        }
+        VNode::VElement(element) => {
+            let attributes = sorted_attributes(element)
+                .into_iter()
+                .map(|(name, value)| format!("{}: {}", json_string(name), json_string(&value)))
+                .collect::<Vec<_>>();
+            let (bubbles, captures) = sorted_event_types(&element.events);
+
+            *json += "{\n";
+            *json += &format!("{}  \"type\": \"element\",\n", indent);
+            *json += &format!(
+                "{}  \"tag_name\": {},\n",
+                indent,
+                json_string(&element.tag_name)
+            );
+            *json += &format!(
+                "{}  \"namespace\": {},\n",
+                indent,
+                json_option(&element.namespace)
+            );
+            *json += &format!(
+                "{}  \"index_id\": {},\n",
+                indent,
+                json_option(&element.index_id)
+            );
+            *json += &format!(
+                "{}  \"attributes\": {{{}}},\n",
+                indent,
+                attributes.join(", ")
+            );
+            *json += &format!(
+                "{}  \"properties\": [{}],\n",
+                indent,
+                json_strings(&sorted_properties(element))
+            );
+            *json += &format!(
+                "{}  \"events\": {{\"bubble\": [{}], \"capture\": [{}]}},\n",
+                indent,
+                json_strings(&bubbles),
+                json_strings(&captures)
+            );
+            *json += &format!("{}  \"refers\": {},\n", indent, element.events.refers.len());
+            *json += &format!("{}  \"children\": ", indent);
+            write_json_list(&element.children, depth + 1, json);
+            *json += &format!("\n{}}}", indent);
+        }
+    }
+}
+
+fn join(names: &[&String]) -> String {
+    names
+        .iter()
+        .map(|name| name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn json_strings(names: &[&String]) -> String {
+    names
+        .iter()
+        .map(|name| json_string(name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn json_option(text: &Option<String>) -> String {
+    text.as_ref()
+        .map(|text| json_string(text))
+        .unwrap_or_else(|| String::from("null"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::html_element::{Attributes, Events};
+
+    fn sample() -> VecDeque<VNode> {
+        v_nodes(vec![Html::element(
+            "ul",
+            Attributes::new()
+                .id("list")
+                .class("a")
+                .class("b")
+                .index_id(String::from("items")),
+            Events::new(),
+            vec![
+                Html::element(
+                    "li",
+                    Attributes::new(),
+                    Events::new(),
+                    vec![Html::text("say \"hi\"")],
+                ),
+                Html::element("br", Attributes::new(), Events::new(), vec![]),
+            ],
+        )])
+    }
+
+    #[test]
+    fn it_serializes_to_text() {
+        assert_eq!(
+            to_text(&sample()),
+            concat!(
+                "<ul @index_id=\"items\" class=\"a b\" id=\"list\">\n",
+                "  <li>\n",
+                "    \"say \\\"hi\\\"\"\n",
+                "  </li>\n",
+                "  <br />\n",
+                "</ul>\n"
+            )
+        );
+    }
+
+    #[test]
+    fn it_serializes_to_json() {
+        assert_snapshot("snapshot_sample_json", &to_json(&sample()));
+    }
+
+    #[test]
+    #[should_panic(expected = "snapshot `snapshot_missing` can not be read")]
+    fn it_fails_without_snapshot_file() {
+        compare_snapshot("snapshot_missing", "", false);
+    }
+
+    #[test]
+    fn it_shows_property_names() {
+        let v_nodes = v_nodes(vec![Html::element(
+            "input",
+            Attributes::new()
+                .type_("checkbox")
+                .property("checked", wasm_bindgen::JsValue::TRUE),
+            Events::new(),
+            vec![],
+        )]);
+        assert_eq!(to_text(&v_nodes), "<input type=\"checkbox\" .checked />\n");
+    }
+}
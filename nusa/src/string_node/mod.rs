@@ -0,0 +1,136 @@
+use crate::dom_node::basic_dom_node::BasicDomComponent;
+use crate::string_renderer::StringRenderer;
+use crate::style::StyleRegistry;
+use crate::{Html, HtmlRenderer};
+use kagura::node::{Msg, NodeCmd, RenderNode, UpdateNode};
+use std::pin::Pin;
+
+/// Max times to render again for synchronous messages, to stop components which send messages on
+/// every render.
+const MAX_RENDER_ROUNDS: usize = 64;
+
+pub struct BasicStringNode {
+    dummy_state: Pin<Box<BasicDomComponent>>,
+    string_renderer: StringRenderer,
+    html_renderer: HtmlRenderer<BasicDomComponent>,
+    render: Box<dyn FnMut(&BasicDomComponent) -> Vec<Html>>,
+    styles: StyleRegistry,
+}
+
+impl BasicStringNode {
+    pub fn new(render: impl FnMut(&BasicDomComponent) -> Vec<Html> + 'static) -> Self {
+        Self {
+            dummy_state: Box::pin(BasicDomComponent::new()),
+            string_renderer: StringRenderer::new(),
+            html_renderer: HtmlRenderer::new(),
+            render: Box::new(render),
+            styles: StyleRegistry::new(),
+        }
+    }
+
+    /// Stylesheets of components which are rendered now, to inline them into the page.
+    pub fn styles(&self) -> String {
+        self.styles.collect()
+    }
+
+    fn render_nodes(&mut self) -> (String, NodeCmd) {
+        let (v_nodes, node_cmd) = self.styles.enter(|| {
+            self.html_renderer
+                .set_children((self.render)(&self.dummy_state.as_ref()));
+            self.html_renderer.render(&self.dummy_state)
+        });
+        (self.string_renderer.render(v_nodes), node_cmd)
+    }
+}
+
+impl UpdateNode for BasicStringNode {
+    fn update(&mut self, msg: Msg) -> NodeCmd {
+        let html_renderer = &mut self.html_renderer;
+        self.styles.enter(|| html_renderer.update(msg))
+    }
+}
+
+impl RenderNode<String> for BasicStringNode {
+    /// Renders the initial state as HTML.
+    /// Messages which are sent synchronously are applied before returning,
+    /// but asynchronous tasks and batches are ignored.
+    /// It stops after `MAX_RENDER_ROUNDS` renders even if messages are sent.
+    fn render(&mut self) -> String {
+        let (mut html, mut node_cmd) = self.render_nodes();
+
+        for _ in 0..MAX_RENDER_ROUNDS {
+            if node_cmd.msgs().is_empty() {
+                break;
+            }
+            let mut msgs = node_cmd.into_msgs();
+            while let Some(msg) = msgs.pop_front() {
+                let mut child_node_cmd = self.update(msg);
+                msgs.append(child_node_cmd.msgs_mut());
+            }
+            let rendered = self.render_nodes();
+            html = rendered.0;
+            node_cmd = rendered.1;
+        }
+
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::html_element::{Attributes, Events};
+    use crate::html_component::Sub;
+    use crate::HtmlComponent;
+    use kagura::component::{Constructor, Render, Update};
+    use kagura::prelude::*;
+
+    struct Ticker {
+        count: usize,
+    }
+
+    impl Component for Ticker {
+        type Props = ();
+        type Msg = ();
+        type Event = ();
+    }
+
+    impl Constructor for Ticker {
+        fn constructor(_props: ()) -> Self {
+            Self { count: 0 }
+        }
+    }
+
+    // sends an event to the parent on every render.
+    impl Update for Ticker {
+        fn on_assemble(self: Pin<&mut Self>) -> Cmd<Self> {
+            Cmd::submit(())
+        }
+
+        fn on_load(mut self: Pin<&mut Self>, _props: ()) -> Cmd<Self> {
+            self.count += 1;
+            Cmd::submit(())
+        }
+    }
+
+    impl Render<Html> for Ticker {
+        type Children = ();
+        fn render(&self, _children: ()) -> Html {
+            Html::span(
+                Attributes::new(),
+                Events::new(),
+                vec![Html::text(self.count.to_string())],
+            )
+        }
+    }
+
+    impl HtmlComponent for Ticker {}
+
+    #[test]
+    fn it_stops_rendering_endless_messages() {
+        let mut node =
+            BasicStringNode::new(|this| vec![Ticker::empty(this, None, (), Sub::map(|_| ()))]);
+
+        assert_eq!(node.render(), format!("<span>{}</span>", MAX_RENDER_ROUNDS));
+    }
+}
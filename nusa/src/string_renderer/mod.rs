@@ -0,0 +1,161 @@
+use crate::v_node::v_element::VAttributes;
+use crate::v_node::{VElement, VText};
+use crate::VNode;
+use std::collections::VecDeque;
+
+pub const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+pub const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+pub struct StringRenderer {}
+
+impl StringRenderer {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn render(&self, nows: VecDeque<VNode>) -> String {
+        let mut html = String::new();
+        Self::render_nodes(&nows, false, &mut html);
+        html
+    }
+
+    fn render_nodes(nows: &VecDeque<VNode>, is_raw_text: bool, html: &mut String) {
+        for now in nows {
+            match now {
+                VNode::VElement(now) => Self::render_element(now, html),
+                VNode::VText(now) => Self::render_text(now, is_raw_text, html),
+                // A real node can not be serialized without DOM.
+                VNode::RNode(..) => {}
+                // A portal is not a part of the parent.
+                VNode::VPortal(..) => {}
+            }
+        }
+    }
+
+    fn render_element(now: &VElement, html: &mut String) {
+        let tag_name = now.tag_name.as_str();
+
+        html.push('<');
+        html.push_str(tag_name);
+        Self::render_attributes(&now.attributes, html);
+
+        if now.namespace.is_some() && now.children.is_empty() {
+            html.push_str(" />");
+            return;
+        }
+
+        html.push('>');
+
+        if now.namespace.is_none() && VOID_ELEMENTS.contains(&tag_name) {
+            return;
+        }
+
+        let is_raw_text = RAW_TEXT_ELEMENTS.contains(&tag_name);
+        Self::render_nodes(&now.children, is_raw_text, html);
+
+        html.push_str("</");
+        html.push_str(tag_name);
+        html.push('>');
+    }
+
+    fn render_attributes(attributes: &VAttributes, html: &mut String) {
+        for (attr_name, attr_values) in attributes {
+            if attr_values.property().is_some() {
+                continue;
+            }
+            html.push(' ');
+            html.push_str(attr_name);
+            html.push_str("=\"");
+            html.push_str(&escape_attribute(&attr_values.to_string()));
+            html.push('"');
+        }
+    }
+
+    fn render_text(now: &VText, is_raw_text: bool, html: &mut String) {
+        if is_raw_text {
+            html.push_str(&now.text);
+        } else {
+            html.push_str(&escape_text(&now.text));
+        }
+    }
+}
+
+impl std::default::Default for StringRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn escape_attribute(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::html::html_element::{Attributes, Events};
+    use crate::string_node::BasicStringNode;
+    use crate::Html;
+    use kagura::node::RenderNode;
+
+    #[test]
+    fn it_renders_html_as_string() {
+        let mut node = BasicStringNode::new(|_| {
+            vec![
+                Html::div(
+                    Attributes::new().class("a").string("title", "\"b\" & c"),
+                    Events::new(),
+                    vec![
+                        Html::text("1 < 2"),
+                        Html::input(Attributes::new().value("x"), Events::new(), vec![]),
+                        Html::element(
+                            "script",
+                            Attributes::new(),
+                            Events::new(),
+                            vec![Html::text("a < b")],
+                        ),
+                    ],
+                ),
+                Html::svg(
+                    Attributes::new(),
+                    Events::new(),
+                    vec![Html::svg_path(Attributes::new(), Events::new(), vec![])],
+                ),
+            ]
+        });
+
+        assert_eq!(
+            node.render(),
+            concat!(
+                r#"<div class="a" title="&quot;b&quot; &amp; c">1 &lt; 2<input value="x">"#,
+                r#"<script>a < b</script></div><svg><path /></svg>"#
+            )
+        );
+    }
+}
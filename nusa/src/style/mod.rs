@@ -0,0 +1,476 @@
+//! Stylesheets which are declared once per component type.
+//!
+//! ```ignore
+//! impl HtmlComponent for Card {
+//!     fn stylesheet() -> Option<Style> {
+//!         Some(
+//!             Style::new()
+//!                 .rule(".base", &[("display", "flex")])
+//!                 .rule(".base:hover .title", &[("color", "red")]),
+//!         )
+//!     }
+//! }
+//!
+//! // in render
+//! Html::div(
+//!     Attributes::new().class(style::class_name::<Self>("base")),
+//!     Events::new(),
+//!     vec![],
+//! )
+//! ```
+//!
+//! A stylesheet is injected on the first mount of the component type, and is removed when the last one is unmounted.
+//! Component types which have identical rules share a scope, so they share one stylesheet.
+//! Each root has its own `StyleRegistry`. `BasicDomNode` injects stylesheets into `<head>`, or the shadow root which contains the entry.
+//! Without target, like SSR, stylesheets are only collected by `StyleRegistry::collect`.
+
+use crate::dom_backend::DomBackend;
+use crate::HtmlComponent;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+thread_local! {
+    static DECLARATIONS: RefCell<HashMap<TypeId, Option<Declaration>>> = RefCell::new(HashMap::new());
+    static CURRENT: RefCell<Vec<StyleRegistry>> = const { RefCell::new(vec![]) };
+    static DEFAULT: StyleRegistry = StyleRegistry::new();
+}
+
+/// Rules of a component. Class names in selectors are scoped by the component type.
+#[derive(Clone, Debug, Default)]
+pub struct Style {
+    rules: Vec<StyleRule>,
+}
+
+#[derive(Clone, Debug)]
+enum StyleRule {
+    Rule {
+        selector: String,
+        declarations: Vec<(String, String)>,
+    },
+    AtRule {
+        prelude: String,
+        style: Style,
+    },
+    Raw(String),
+}
+
+/// A mounted stylesheet. It is unmounted when dropped.
+pub struct StyleHandle {
+    scope: String,
+    registry: StyleRegistry,
+}
+
+/// Stylesheets which are mounted in a root.
+#[derive(Clone)]
+pub struct StyleRegistry {
+    state: Rc<RefCell<StyleRegistryState>>,
+}
+
+struct StyleRegistryState {
+    target: Option<Box<dyn StyleTarget>>,
+    sheets: Vec<Sheet>,
+}
+
+/// A node which `<style>` elements are appended to through `DomBackend`.
+trait StyleTarget {
+    fn inject(&self, declaration: &Declaration) -> Box<dyn Any>;
+    fn remove(&self, element: &dyn Any);
+}
+
+struct BackendStyleTarget<B: DomBackend> {
+    backend: B,
+    node: B::Node,
+}
+
+/// A stylesheet of a component type, which is scoped once.
+#[derive(Clone)]
+struct Declaration {
+    scope: String,
+    css: String,
+}
+
+/// A stylesheet of a scope, which is shared by component types whose rules are identical.
+struct Sheet {
+    declaration: Declaration,
+    count: usize,
+    element: Option<Box<dyn Any>>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self { rules: vec![] }
+    }
+
+    pub fn rule(mut self, selector: impl Into<String>, declarations: &[(&str, &str)]) -> Self {
+        self.rules.push(StyleRule::Rule {
+            selector: selector.into(),
+            declarations: declarations
+                .iter()
+                .map(|(name, value)| (String::from(*name), String::from(*value)))
+                .collect(),
+        });
+        self
+    }
+
+    /// Adds a nested rule like `@media (max-width: 600px)`.
+    pub fn at_rule(mut self, prelude: impl Into<String>, style: Style) -> Self {
+        self.rules.push(StyleRule::AtRule {
+            prelude: prelude.into(),
+            style,
+        });
+        self
+    }
+
+    /// Adds CSS as it is. Class names are not scoped.
+    pub fn raw(mut self, css: impl Into<String>) -> Self {
+        self.rules.push(StyleRule::Raw(css.into()));
+        self
+    }
+
+    /// A scope which is made from the rules by FNV-1a.
+    pub fn scope(&self) -> String {
+        let hash = self.to_css("").bytes().fold(0x811c9dc5_u32, |hash, byte| {
+            (hash ^ byte as u32).wrapping_mul(0x01000193)
+        });
+        format!("s{:08x}", hash)
+    }
+
+    pub fn to_css(&self, scope: &str) -> String {
+        self.rules
+            .iter()
+            .map(|rule| match rule {
+                StyleRule::Rule {
+                    selector,
+                    declarations,
+                } => {
+                    let declarations = declarations
+                        .iter()
+                        .map(|(name, value)| format!("{}:{};", name, value))
+                        .collect::<String>();
+                    format!("{}{{{}}}", scope_selector(selector, scope), declarations)
+                }
+                StyleRule::AtRule { prelude, style } => {
+                    format!("{}{{{}}}", prelude, style.to_css(scope))
+                }
+                StyleRule::Raw(css) => css.clone(),
+            })
+            .collect()
+    }
+}
+
+/// The prefix of scoped class names of `C`.
+/// It is a hash of the stylesheet, so it is same in SSR and browser.
+pub fn scope<C: HtmlComponent>() -> String {
+    declare::<C>(C::stylesheet)
+        .map(|declaration| declaration.scope)
+        .unwrap_or_else(|| Style::new().scope())
+}
+
+/// A class name which is scoped by `C`.
+pub fn class_name<C: HtmlComponent>(name: &str) -> String {
+    format!("{}-{}", scope::<C>(), name)
+}
+
+/// Scopes the stylesheet of `C` on the first call.
+fn declare<C: 'static>(stylesheet: impl FnOnce() -> Option<Style>) -> Option<Declaration> {
+    let type_id = TypeId::of::<C>();
+    if let Some(declaration) =
+        DECLARATIONS.with(|declarations| declarations.borrow().get(&type_id).cloned())
+    {
+        return declaration;
+    }
+
+    let declaration = stylesheet().map(|style| {
+        let scope = style.scope();
+        let css = style.to_css(&scope);
+        Declaration { scope, css }
+    });
+    DECLARATIONS.with(|declarations| {
+        declarations
+            .borrow_mut()
+            .insert(type_id, declaration.clone())
+    });
+    declaration
+}
+
+impl StyleHandle {
+    /// Mounts a stylesheet of `C` to the current registry. `stylesheet` is called only once per type.
+    pub fn mount<C: 'static>(stylesheet: impl FnOnce() -> Option<Style>) -> Option<Self> {
+        let declaration = declare::<C>(stylesheet)?;
+        let scope = declaration.scope.clone();
+        let registry = StyleRegistry::current();
+        registry.mount(declaration);
+        Some(Self { scope, registry })
+    }
+}
+
+impl std::ops::Drop for StyleHandle {
+    fn drop(&mut self) {
+        self.registry.release(&self.scope);
+    }
+}
+
+impl StyleRegistry {
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(StyleRegistryState {
+                target: None,
+                sheets: vec![],
+            })),
+        }
+    }
+
+    /// The registry of `enter`, or the default registry which has no target.
+    fn current() -> Self {
+        CURRENT
+            .with(|current| current.borrow().last().cloned())
+            .unwrap_or_else(|| DEFAULT.with(Self::clone))
+    }
+
+    /// Mounts stylesheets of components which are created in `f` to this registry.
+    pub fn enter<T>(&self, f: impl FnOnce() -> T) -> T {
+        CURRENT.with(|current| current.borrow_mut().push(self.clone()));
+        let res = f();
+        CURRENT.with(|current| current.borrow_mut().pop());
+        res
+    }
+
+    /// Sets the node which stylesheets are appended to: `<head>` or a shadow root.
+    /// Stylesheets which are already mounted are moved to it.
+    pub fn set_target<B: DomBackend>(&self, backend: &B, target: Option<B::Node>) {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+        for sheet in &mut state.sheets {
+            if let (Some(target), Some(element)) = (&state.target, sheet.element.take()) {
+                target.remove(element.as_ref());
+            }
+        }
+
+        state.target = target.map(|node| {
+            Box::new(BackendStyleTarget {
+                backend: backend.clone(),
+                node,
+            }) as Box<dyn StyleTarget>
+        });
+
+        for sheet in &mut state.sheets {
+            sheet.element = state
+                .target
+                .as_ref()
+                .map(|target| target.inject(&sheet.declaration));
+        }
+    }
+
+    /// Stylesheets which are mounted now as `<style>` elements, to inline them into a page rendered by SSR.
+    pub fn collect(&self) -> String {
+        self.state
+            .borrow()
+            .sheets
+            .iter()
+            .map(|sheet| {
+                format!(
+                    r#"<style data-nusa-style="{}">{}</style>"#,
+                    sheet.declaration.scope, sheet.declaration.css
+                )
+            })
+            .collect()
+    }
+
+    fn mount(&self, declaration: Declaration) {
+        let mut state = self.state.borrow_mut();
+        if let Some(sheet) = state
+            .sheets
+            .iter_mut()
+            .find(|sheet| sheet.declaration.scope == declaration.scope)
+        {
+            sheet.count += 1;
+            return;
+        }
+
+        let element = state
+            .target
+            .as_ref()
+            .map(|target| target.inject(&declaration));
+        state.sheets.push(Sheet {
+            declaration,
+            count: 1,
+            element,
+        });
+    }
+
+    fn release(&self, scope: &str) {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+        if let Some(sheet) = state
+            .sheets
+            .iter_mut()
+            .find(|sheet| sheet.declaration.scope == scope)
+        {
+            sheet.count -= 1;
+            if sheet.count > 0 {
+                return;
+            }
+        }
+
+        let target = &state.target;
+        state.sheets.retain(|sheet| {
+            if sheet.declaration.scope != scope {
+                return true;
+            }
+            if let (Some(target), Some(element)) = (target, &sheet.element) {
+                target.remove(element.as_ref());
+            }
+            false
+        });
+    }
+}
+
+impl std::default::Default for StyleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: DomBackend> StyleTarget for BackendStyleTarget<B> {
+    fn inject(&self, declaration: &Declaration) -> Box<dyn Any> {
+        let element = self.backend.create_element("style");
+        self.backend
+            .set_attribute(&element, "data-nusa-style", &declaration.scope);
+        let text = self.backend.create_text_node(&declaration.css);
+        self.backend.insert_before(&element, &text, None);
+        self.backend.insert_before(&self.node, &element, None);
+        Box::new(element)
+    }
+
+    fn remove(&self, element: &dyn Any) {
+        if let Some(element) = element.downcast_ref::<B::Node>() {
+            self.backend.remove_child(&self.node, element);
+        }
+    }
+}
+
+/// Prefixes class names in `selector` with `scope`, except in attribute selectors and strings.
+fn scope_selector(selector: &str, scope: &str) -> String {
+    let mut scoped = String::new();
+    let mut bracket_depth = 0;
+    let mut quote = None;
+    let mut chars = selector.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        scoped.push(c);
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '[') => bracket_depth += 1,
+            (None, ']') => bracket_depth -= 1,
+            (None, '.')
+                if bracket_depth == 0
+                    && chars
+                        .peek()
+                        .map(|c| c.is_alphabetic() || *c == '_' || *c == '-')
+                        .unwrap_or(false) =>
+            {
+                scoped.push_str(scope);
+                scoped.push('-');
+            }
+            _ => {}
+        }
+    }
+
+    scoped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom_backend::MemoryDomBackend;
+
+    struct Card;
+    struct Badge;
+    struct Plain;
+
+    fn card_style() -> Option<Style> {
+        Some(
+            Style::new()
+                .rule(".base:hover > .title", &[("color", "red")])
+                .rule(r#"a[href$=".pdf"]"#, &[("color", "blue")])
+                .at_rule(
+                    "@media (max-width: 600px)",
+                    Style::new().rule(".base", &[("display", "none")]),
+                ),
+        )
+    }
+
+    #[test]
+    fn it_scopes_class_names_by_rules() {
+        let scope = card_style().unwrap().scope();
+        assert_eq!(scope, card_style().unwrap().scope());
+        assert_eq!(Style::new().scope(), "s811c9dc5");
+        assert_eq!(
+            card_style().unwrap().to_css(&scope),
+            format!(
+                r#".{0}-base:hover > .{0}-title{{color:red;}}a[href$=".pdf"]{{color:blue;}}@media (max-width: 600px){{.{0}-base{{display:none;}}}}"#,
+                scope
+            )
+        );
+    }
+
+    #[test]
+    fn it_keeps_stylesheets_while_mounted() {
+        let registry = StyleRegistry::new();
+        let other = StyleRegistry::new();
+        let (first, second) = registry.enter(|| {
+            (
+                StyleHandle::mount::<Card>(card_style),
+                StyleHandle::mount::<Card>(|| panic!("stylesheet is declared once")),
+            )
+        });
+        assert!(registry
+            .enter(|| StyleHandle::mount::<Plain>(|| None))
+            .is_none());
+        assert_eq!(registry.collect().matches("<style").count(), 1);
+        assert_eq!(other.collect(), "");
+
+        drop(first);
+        assert_eq!(registry.collect().matches("<style").count(), 1);
+
+        drop(second);
+        assert_eq!(registry.collect(), "");
+    }
+
+    #[test]
+    fn it_shares_stylesheets_of_identical_rules() {
+        let backend = MemoryDomBackend::new();
+        let head = backend.create_root();
+        let registry = StyleRegistry::new();
+        registry.set_target(&backend, Some(head.clone()));
+
+        let (card, badge) = registry.enter(|| {
+            (
+                StyleHandle::mount::<Card>(card_style),
+                StyleHandle::mount::<Badge>(card_style),
+            )
+        });
+        assert_eq!(registry.collect().matches("<style").count(), 1);
+        assert_eq!(head.children().len(), 1);
+        assert_eq!(
+            head.children()[0].attribute("data-nusa-style"),
+            Some(card_style().unwrap().scope())
+        );
+
+        drop(card);
+        assert_eq!(head.children().len(), 1);
+
+        let other_head = backend.create_root();
+        registry.set_target(&backend, Some(other_head.clone()));
+        assert!(head.children().is_empty());
+        assert_eq!(other_head.children().len(), 1);
+
+        drop(badge);
+        assert!(other_head.children().is_empty());
+        assert_eq!(registry.collect(), "");
+    }
+}
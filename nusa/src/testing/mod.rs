@@ -0,0 +1,454 @@
+//! A harness to test components in a browser, or natively with `MemoryDomBackend`.
+//!
+//! ```ignore
+//! use nusa::testing::Harness;
+//! use wasm_bindgen_test::*;
+//!
+//! wasm_bindgen_test_configure!(run_in_browser);
+//!
+//! #[wasm_bindgen_test]
+//! async fn it_counts_up() {
+//!     let mut harness = Harness::mount::<Counter>(counter::Props {}).await;
+//!     let button = harness.query("button").unwrap();
+//!
+//!     harness.click(&button).await;
+//!
+//!     assert_eq!(harness.html(), "<button>count:1</button>");
+//! }
+//! ```
+//!
+//! In `MemoryDomBackend`, handlers read events through `DomBackend`, like `VEvent::event_target`
+//! and `VEvent::key`. Data of specific interfaces, like `clientX` of `MouseEvent`, needs a browser.
+
+use crate::dom_backend::memory_dom_backend::MemoryNode;
+use crate::dom_backend::{DomBackend, MemoryDomBackend, WebSysDomBackend};
+use crate::dom_node::basic_dom_node::BasicDomComponent;
+use crate::dom_node::BasicDomNode;
+use crate::html_component::Sub;
+use crate::{Html, HtmlComponent};
+use kagura::Runtime;
+use wasm_bindgen::JsCast;
+
+pub struct Harness<B: DomBackend = WebSysDomBackend> {
+    runtime: Runtime<BasicDomNode<B>>,
+    backend: B,
+    root: B::Node,
+    // a browser runs microtasks of listeners before a macrotask.
+    waits_macrotask: bool,
+}
+
+impl<B: DomBackend> Harness<B> {
+    /// Mounts htmls into `root` of `backend`, and waits until it is rendered.
+    pub async fn mount_with_backend(
+        backend: B,
+        root: B::Node,
+        render: impl FnMut(&BasicDomComponent) -> Vec<Html> + 'static,
+    ) -> Self {
+        let node = BasicDomNode::with_backend(backend.clone(), root.clone(), render);
+        let mut harness = Self {
+            runtime: Runtime::new(node),
+            backend,
+            root,
+            waits_macrotask: false,
+        };
+        harness.idle().await;
+        harness
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Waits until the runtime has no ready message.
+    pub async fn idle(&mut self) {
+        loop {
+            if self.waits_macrotask {
+                tick().await;
+            }
+            if !self.runtime.run_until_idle().await {
+                break;
+            }
+        }
+    }
+}
+
+impl Harness {
+    /// Mounts a component `C` into a detached `div`, and waits until it is rendered.
+    pub async fn mount<C: HtmlComponent>(props: C::Props) -> Self
+    where
+        C::Props: Clone,
+    {
+        Self::mount_with(move |this| vec![C::empty(this, None, props.clone(), Sub::none())]).await
+    }
+
+    /// Mounts htmls into a detached `div`, like `BasicDomNode::new`.
+    pub async fn mount_with(render: impl FnMut(&BasicDomComponent) -> Vec<Html> + 'static) -> Self {
+        let root = web_sys::window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("div")
+            .unwrap();
+        let node = BasicDomNode::new(root.clone().into(), render);
+        let mut harness = Self {
+            runtime: Runtime::new(node),
+            backend: WebSysDomBackend::new(),
+            root: root.into(),
+            waits_macrotask: true,
+        };
+        harness.idle().await;
+        harness
+    }
+
+    pub fn root(&self) -> &web_sys::Element {
+        self.root.unchecked_ref()
+    }
+
+    /// Rendered HTML in the root.
+    pub fn html(&self) -> String {
+        self.root().inner_html()
+    }
+
+    pub fn query(&self, selector: &str) -> Option<web_sys::Element> {
+        self.root().query_selector(selector).ok().flatten()
+    }
+
+    pub fn query_all(&self, selector: &str) -> Vec<web_sys::Element> {
+        let mut elements = vec![];
+        if let Ok(nodes) = self.root().query_selector_all(selector) {
+            for i in 0..nodes.length() {
+                if let Some(element) = nodes.get(i).and_then(|node| node.dyn_into().ok()) {
+                    elements.push(element);
+                }
+            }
+        }
+        elements
+    }
+
+    /// Finds the first and the deepest element whose trimmed text is `text`.
+    pub fn find_by_text(&self, text: &str) -> Option<web_sys::Element> {
+        let mut found: Option<web_sys::Element> = None;
+        for element in self.query_all("*") {
+            let is_matched = element
+                .text_content()
+                .map(|element_text| element_text.trim() == text)
+                .unwrap_or(false);
+            if !is_matched {
+                continue;
+            }
+            match &found {
+                Some(parent) if !parent.contains(Some(&element)) => break,
+                _ => found = Some(element),
+            }
+        }
+        found
+    }
+
+    /// Dispatches an event to `target` through `DomEvents`, and waits until the runtime is idle.
+    pub async fn dispatch(&mut self, target: &web_sys::EventTarget, event: &web_sys::Event) {
+        let _ = target.dispatch_event(event);
+        self.idle().await;
+    }
+
+    pub async fn click(&mut self, target: &web_sys::Element) {
+        let init = web_sys::MouseEventInit::new();
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        let event = web_sys::MouseEvent::new_with_mouse_event_init_dict("click", &init).unwrap();
+        self.dispatch(target, &event).await;
+    }
+
+    /// Sets `value` of `input` or `textarea`, and dispatches `input` event.
+    pub async fn input(&mut self, target: &web_sys::Element, value: &str) {
+        if let Some(target) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+            target.set_value(value);
+        } else if let Some(target) = target.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            target.set_value(value);
+        }
+        let init = web_sys::EventInit::new();
+        init.set_bubbles(true);
+        let event = web_sys::Event::new_with_event_init_dict("input", &init).unwrap();
+        self.dispatch(target, &event).await;
+    }
+
+    pub async fn key_down(&mut self, target: &web_sys::Element, key: &str) {
+        self.keyboard(target, "keydown", key).await;
+    }
+
+    pub async fn key_up(&mut self, target: &web_sys::Element, key: &str) {
+        self.keyboard(target, "keyup", key).await;
+    }
+
+    pub async fn keyboard(&mut self, target: &web_sys::Element, event_type: &str, key: &str) {
+        let init = web_sys::KeyboardEventInit::new();
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        init.set_key(key);
+        let event =
+            web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(event_type, &init).unwrap();
+        self.dispatch(target, &event).await;
+    }
+}
+
+impl Harness<MemoryDomBackend> {
+    /// Mounts a component `C` into a `div` of `MemoryDomBackend`, and waits until it is rendered.
+    pub async fn mount_in_memory<C: HtmlComponent>(props: C::Props) -> Self
+    where
+        C::Props: Clone,
+    {
+        Self::mount_in_memory_with(move |this| {
+            vec![C::empty(this, None, props.clone(), Sub::none())]
+        })
+        .await
+    }
+
+    /// Mounts htmls into a `div` of `MemoryDomBackend`.
+    pub async fn mount_in_memory_with(
+        render: impl FnMut(&BasicDomComponent) -> Vec<Html> + 'static,
+    ) -> Self {
+        let backend = MemoryDomBackend::new();
+        let root = backend.create_root();
+        Self::mount_with_backend(backend, root, render).await
+    }
+
+    pub fn root(&self) -> &MemoryNode {
+        &self.root
+    }
+
+    /// Rendered HTML in the root.
+    pub fn html(&self) -> String {
+        self.root.inner_html()
+    }
+
+    pub fn query(&self, selector: &str) -> Option<MemoryNode> {
+        self.root.query_selector(selector)
+    }
+
+    pub fn query_all(&self, selector: &str) -> Vec<MemoryNode> {
+        self.root.query_selector_all(selector)
+    }
+
+    /// Finds the first and the deepest element whose trimmed text is `text`.
+    pub fn find_by_text(&self, text: &str) -> Option<MemoryNode> {
+        fn find(node: &MemoryNode, text: &str) -> Option<MemoryNode> {
+            node.children()
+                .iter()
+                .filter(|child| child.tag_name().is_some())
+                .find_map(|child| {
+                    find(child, text)
+                        .or_else(|| (child.text_content().trim() == text).then(|| child.clone()))
+                })
+        }
+        find(&self.root, text)
+    }
+
+    /// Dispatches an event to `target`, and waits until the runtime is idle.
+    /// Returns `false` if a handler cancels the event.
+    pub async fn dispatch(&mut self, target: &MemoryNode, event_type: &str, bubbles: bool) -> bool {
+        let is_not_canceled = self.backend.dispatch_event(target, event_type, bubbles);
+        self.idle().await;
+        is_not_canceled
+    }
+
+    pub async fn click(&mut self, target: &MemoryNode) {
+        self.dispatch(target, "click", true).await;
+    }
+
+    /// Sets `value` of `input`, `textarea` or `select`, and dispatches `input` event.
+    pub async fn input(&mut self, target: &MemoryNode, value: &str) {
+        target.set_value(value);
+        self.dispatch(target, "input", true).await;
+    }
+
+    pub async fn key_down(&mut self, target: &MemoryNode, key: &str) {
+        self.keyboard(target, "keydown", key).await;
+    }
+
+    pub async fn key_up(&mut self, target: &MemoryNode, key: &str) {
+        self.keyboard(target, "keyup", key).await;
+    }
+
+    pub async fn keyboard(&mut self, target: &MemoryNode, event_type: &str, key: &str) {
+        self.backend.dispatch_key_event(target, event_type, key);
+        self.idle().await;
+    }
+}
+
+/// Waits for a macrotask, so that microtasks spawned by event listeners are done.
+async fn tick() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let _ = web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0);
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::html_element::{Attributes, Events};
+    use kagura::component::{Constructor, Render, Update};
+    use kagura::prelude::*;
+    use std::pin::Pin;
+
+    struct Counter {
+        count: usize,
+    }
+
+    impl Component for Counter {
+        type Props = ();
+        type Msg = ();
+        type Event = ();
+    }
+
+    impl Constructor for Counter {
+        fn constructor(_props: ()) -> Self {
+            Self { count: 0 }
+        }
+    }
+
+    impl Update for Counter {
+        fn update(mut self: Pin<&mut Self>, _msg: ()) -> Cmd<Self> {
+            self.count += 1;
+            Cmd::none()
+        }
+    }
+
+    impl Render<Html> for Counter {
+        type Children = ();
+        fn render(&self, _children: ()) -> Html {
+            Html::button(
+                Attributes::new(),
+                Events::new().on("click", self, |e| e.prevent_default()),
+                vec![Html::text(format!("count:{}", self.count))],
+            )
+        }
+    }
+
+    impl HtmlComponent for Counter {}
+
+    struct TextField {
+        text: String,
+        submitted: Vec<String>,
+    }
+
+    enum TextFieldMsg {
+        Input(String),
+        Submit,
+        None,
+    }
+
+    impl Component for TextField {
+        type Props = ();
+        type Msg = TextFieldMsg;
+        type Event = ();
+    }
+
+    impl Constructor for TextField {
+        fn constructor(_props: ()) -> Self {
+            Self {
+                text: String::new(),
+                submitted: vec![],
+            }
+        }
+    }
+
+    impl Update for TextField {
+        fn update(mut self: Pin<&mut Self>, msg: TextFieldMsg) -> Cmd<Self> {
+            match msg {
+                TextFieldMsg::Input(text) => self.text = text,
+                TextFieldMsg::Submit => {
+                    let text = std::mem::take(&mut self.text);
+                    self.submitted.push(text);
+                }
+                TextFieldMsg::None => {}
+            }
+            Cmd::none()
+        }
+    }
+
+    impl Render<Html> for TextField {
+        type Children = ();
+        fn render(&self, _children: ()) -> Html {
+            Html::div(
+                Attributes::new(),
+                Events::new(),
+                vec![
+                    Html::input(
+                        Attributes::new().value(&self.text),
+                        Events::new()
+                            .on_input(self, TextFieldMsg::Input)
+                            .on_keydown(self, |e| match e.key().as_str() {
+                                "Enter" => TextFieldMsg::Submit,
+                                _ => TextFieldMsg::None,
+                            }),
+                        vec![],
+                    ),
+                    Html::button(
+                        Attributes::new().class("submit"),
+                        Events::new().on_click(self, |_| TextFieldMsg::Submit),
+                        vec![Html::text("submit")],
+                    ),
+                    Html::ul(
+                        Attributes::new(),
+                        Events::new(),
+                        self.submitted
+                            .iter()
+                            .map(|text| {
+                                Html::li(Attributes::new(), Events::new(), vec![Html::text(text)])
+                            })
+                            .collect(),
+                    ),
+                ],
+            )
+        }
+    }
+
+    impl HtmlComponent for TextField {}
+
+    #[test]
+    fn it_counts_up_in_memory() {
+        async_std::task::block_on(async {
+            let mut harness = Harness::mount_in_memory::<Counter>(()).await;
+            let button = harness.find_by_text("count:0").unwrap();
+
+            harness.click(&button).await;
+
+            assert_eq!(harness.html(), "<button>count:1</button>");
+        });
+    }
+
+    #[test]
+    fn it_handles_back_to_back_events_synchronously() {
+        async_std::task::block_on(async {
+            let mut harness = Harness::mount_in_memory::<Counter>(()).await;
+            let button = harness.find_by_text("count:0").unwrap();
+
+            assert!(!harness.backend().dispatch_event(&button, "click", true));
+            assert!(!harness.backend().dispatch_event(&button, "click", true));
+            harness.idle().await;
+
+            assert_eq!(harness.html(), "<button>count:2</button>");
+        });
+    }
+
+    #[test]
+    fn it_handles_typed_events_in_memory() {
+        async_std::task::block_on(async {
+            let mut harness = Harness::mount_in_memory::<TextField>(()).await;
+            let input = harness.query("div > input").unwrap();
+            let button = harness.query("div button.submit").unwrap();
+
+            harness.input(&input, "a").await;
+            harness.click(&button).await;
+            harness.input(&input, "b").await;
+            harness.key_down(&input, "Enter").await;
+
+            let items = harness.query_all("ul li");
+            let items = items.iter().map(|item| item.text_content());
+            assert_eq!(items.collect::<Vec<_>>(), vec!["a", "b"]);
+            assert_eq!(input.value().as_deref(), Some(""));
+        });
+    }
+}
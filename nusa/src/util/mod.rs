@@ -1,3 +1,5 @@
+pub mod json;
 pub mod mix;
 
+pub use json::json_string;
 pub use mix::mix;
@@ -1,13 +1,19 @@
 pub mod v_element;
+pub mod v_event_target;
+pub mod v_portal;
 pub mod v_text;
 
 pub use v_element::VElement;
+pub use v_event_target::VEventTarget;
+pub use v_portal::VPortal;
 pub use v_text::VText;
 
+#[allow(clippy::large_enum_variant)]
 pub enum VNode {
     VElement(VElement),
     VText(VText),
     RNode(web_sys::Node),
+    VPortal(VPortal),
 }
 
 impl VNode {
@@ -16,6 +22,7 @@ impl VNode {
             Self::VText(v_node) => Self::VText(v_node.clone()),
             Self::VElement(v_node) => Self::VElement(v_node.as_rendered()),
             Self::RNode(r_node) => Self::RNode(r_node.clone()),
+            Self::VPortal(v_node) => Self::VPortal(v_node.as_rendered()),
         }
     }
 }
@@ -26,6 +33,7 @@ impl std::fmt::Debug for VNode {
             Self::RNode(..) => write!(f, "[RNode]"),
             Self::VText(text) => text.fmt(f),
             Self::VElement(element) => element.fmt(f),
+            Self::VPortal(portal) => portal.fmt(f),
         }
     }
 }
@@ -1,9 +1,13 @@
-use super::VNode;
+use super::v_event_target::VEventSource;
+use super::{VEventTarget, VNode};
+use crate::dom_backend::DomBackend;
+use crate::dom_events::DomEvent;
+use crate::dom_ref::RefTarget;
 use kagura::node::Msg;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::rc::Rc;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 
 pub struct VElement {
     pub tag_name: Rc<String>,
@@ -12,20 +16,34 @@ pub struct VElement {
     pub children: VecDeque<VNode>,
     pub index_id: Option<String>,
     pub namespace: Option<String>,
+    /// Live values of properties before `DomRenderer` writes them, which are restored on removal.
+    /// It is shared with the rendered copy.
+    pub property_defaults: VPropertyDefaults,
 }
 
-pub type VAttributes = HashMap<String, VAttributeValues>;
+pub type VPropertyDefaults = Rc<RefCell<HashMap<String, Rc<JsValue>>>>;
+
+/// Attributes in insertion order.
+#[derive(Clone, PartialEq, Debug)]
+pub struct VAttributes {
+    data: Vec<(String, VAttributeValues)>,
+    index: HashMap<String, usize>,
+}
 
 #[derive(Debug)]
 pub struct VEvents {
     pub events: HashMap<String, VEventHandlers>,
     pub refers: Vec<VReferHandler>,
+    pub node_refs: Vec<VNodeRef>,
+    pub directs: VDirectListeners,
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct VAttributeValues {
     pub values: VecDeque<VAttributeValue>,
     pub delimiter: String,
+    /// The namespace of a prefixed attribute like `xlink:href`, which is resolved by `HtmlRenderer`.
+    pub namespace: Option<String>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -35,23 +53,62 @@ pub enum VAttributeValue {
     Int(i64),
     Num(f64),
     None,
+    /// A DOM property, which is written by `Reflect.set` instead of `setAttribute`.
+    /// It is shared, so that rendered trees are cloned without JS.
+    Property(Rc<JsValue>),
+    /// A declaration of `style`, which is written by `style.setProperty`.
+    Style(Rc<String>, Rc<String>),
 }
 
 pub struct VEventHandlers {
     pub bubbles: Vec<VEventHandler>,
     pub captures: Vec<VEventHandler>,
+    /// If `Some`, handlers are added to the element directly, not to the root element.
+    pub direct: Option<VListenerOptions>,
 }
 
-pub type VEventHandler = Box<dyn FnOnce(VEvent<web_sys::Event>) -> Msg>;
+/// Options of a listener which is added to the element directly.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct VListenerOptions {
+    pub passive: bool,
+    /// The listener is removed after the first event, and is not added again while the element is kept.
+    pub once: bool,
+}
+
+/// Listeners which are added to an element directly.
+/// It is shared with the rendered copy of the element, and listeners are removed when it is dropped.
+#[derive(Clone)]
+pub struct VDirectListeners {
+    listeners: Rc<RefCell<VDirectListenerMap>>,
+}
+
+type VDirectListenerMap = HashMap<(String, bool), (VListenerOptions, DomEvent)>;
+
+/// Returns `None` if the event is ignored.
+pub type VEventHandler = Box<dyn FnMut(VEvent<web_sys::Event>) -> Option<Msg>>;
 
 pub struct VReferHandler {
     pub target: usize,
     handler: Option<Box<dyn FnOnce(web_sys::Node) -> Msg>>,
 }
 
+/// A `NodeRef` of the element, which `DomRenderer` sets when the element is created or replaced,
+/// and clears when it is removed.
+pub struct VNodeRef {
+    node: Rc<RefCell<Option<RefTarget>>>,
+    on_attach: Option<Box<dyn FnOnce(web_sys::Node) -> Option<Msg>>>,
+    // shared with the rendered copy, because a removed element has only the rendered copy.
+    on_detach: VDetachHandler,
+}
+
+type VDetachHandler = Rc<RefCell<Option<Box<dyn FnOnce() -> Msg>>>>;
+
 pub struct VEvent<T> {
     data: T,
     stop_propagation: Rc<Cell<bool>>,
+    stop_immediate_propagation: Rc<Cell<bool>>,
+    // reads the event through `DomBackend`. `None` reads `data`.
+    source: Option<Rc<dyn VEventSource>>,
 }
 
 impl VElement {
@@ -67,18 +124,161 @@ impl VElement {
                 .collect(),
             index_id: self.index_id.clone(),
             namespace: self.namespace.clone(),
+            property_defaults: Rc::clone(&self.property_defaults),
         }
     }
 }
 
 impl std::fmt::Debug for VElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut attributes = self.attributes.iter().collect::<Vec<_>>();
+        attributes.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut event_types = self.events.events.keys().collect::<Vec<_>>();
+        event_types.sort();
+
         f.debug_struct(&self.tag_name)
+            .field("namespace", &self.namespace)
+            .field("index_id", &self.index_id)
+            .field("attributes", &attributes)
+            .field("events", &event_types)
             .field("children", &self.children)
             .finish()
     }
 }
 
+impl VAttributes {
+    pub fn new() -> Self {
+        Self {
+            data: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&VAttributeValues> {
+        self.index.get(name).map(|i| &self.data[*i].1)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut VAttributeValues> {
+        self.index.get(name).map(|i| &mut self.data[*i].1)
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.index.contains_key(name)
+    }
+
+    /// Replaces values of `name` without changing its position, or appends it.
+    pub fn insert(&mut self, name: String, values: VAttributeValues) -> Option<VAttributeValues> {
+        if let Some(i) = self.index.get(&name) {
+            Some(std::mem::replace(&mut self.data[*i].1, values))
+        } else {
+            self.index.insert(name.clone(), self.data.len());
+            self.data.push((name, values));
+            None
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<VAttributeValues> {
+        let i = self.index.remove(name)?;
+        let (_, values) = self.data.remove(i);
+        for (name, _) in &self.data[i..] {
+            if let Some(j) = self.index.get_mut(name) {
+                *j -= 1;
+            }
+        }
+        Some(values)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &VAttributeValues)> {
+        self.data.iter().map(|(name, values)| (name, values))
+    }
+}
+
+impl std::default::Default for VAttributes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntoIterator for VAttributes {
+    type Item = (String, VAttributeValues);
+    type IntoIter = std::vec::IntoIter<(String, VAttributeValues)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a VAttributes {
+    type Item = (&'a String, &'a VAttributeValues);
+    type IntoIter = std::iter::Map<
+        std::slice::Iter<'a, (String, VAttributeValues)>,
+        fn(&'a (String, VAttributeValues)) -> (&'a String, &'a VAttributeValues),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter().map(|(name, values)| (name, values))
+    }
+}
+
+impl VAttributeValues {
+    /// Returns the value if it is a DOM property.
+    /// Only the last value is read, because `Attributes::property` replaces other values of the name.
+    pub fn property(&self) -> Option<&Rc<JsValue>> {
+        match self.values.back() {
+            Some(VAttributeValue::Property(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Tokens of `class` without duplicates.
+    pub fn class_names(&self) -> Vec<String> {
+        let mut class_names: Vec<String> = vec![];
+        for value in &self.values {
+            for class_name in value.to_string().split_whitespace() {
+                if !class_names.iter().any(|c| c == class_name) {
+                    class_names.push(String::from(class_name));
+                }
+            }
+        }
+        class_names
+    }
+
+    /// Declarations of `style`. A later declaration overrides the former one of the same property.
+    pub fn style_properties(&self) -> Vec<(String, String)> {
+        let mut properties: Vec<(String, String)> = vec![];
+        let mut set = |name: &str, value: &str| {
+            if let Some(property) = properties.iter_mut().find(|(n, _)| n == name) {
+                property.1 = String::from(value);
+            } else {
+                properties.push((String::from(name), String::from(value)));
+            }
+        };
+
+        for value in &self.values {
+            if let VAttributeValue::Style(name, value) = value {
+                set(name.trim(), value.trim());
+            } else {
+                for declaration in value.to_string().split(';') {
+                    if let Some((name, value)) = declaration.split_once(':') {
+                        set(name.trim(), value.trim());
+                    }
+                }
+            }
+        }
+
+        properties
+    }
+}
+
 impl std::string::ToString for VAttributeValues {
     fn to_string(&self) -> String {
         self.values
@@ -97,6 +297,8 @@ impl std::string::ToString for VAttributeValue {
             Self::Num(v) => v.to_string(),
             Self::Nut(v) => v.to_string(),
             Self::None => String::from(""),
+            Self::Property(v) => v.as_string().unwrap_or_default(),
+            Self::Style(name, value) => format!("{}:{}", name, value),
         }
     }
 }
@@ -106,6 +308,8 @@ impl VEvents {
         Self {
             events: HashMap::new(),
             refers: vec![],
+            node_refs: vec![],
+            directs: VDirectListeners::new(),
         }
     }
 
@@ -117,6 +321,12 @@ impl VEvents {
                 .iter()
                 .map(|refer| refer.as_rendered())
                 .collect(),
+            node_refs: self
+                .node_refs
+                .iter()
+                .map(|node_ref| node_ref.as_rendered())
+                .collect(),
+            directs: self.directs.clone(),
         }
     }
 }
@@ -126,10 +336,64 @@ impl VEventHandlers {
         Self {
             bubbles: vec![],
             captures: vec![],
+            direct: None,
         }
     }
 }
 
+impl VDirectListeners {
+    pub fn new() -> Self {
+        Self {
+            listeners: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.listeners.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listeners.borrow().is_empty()
+    }
+
+    /// Takes a listener of `event_type` in `capture` phase.
+    pub fn remove(&self, event_type: &str, capture: bool) -> Option<(VListenerOptions, DomEvent)> {
+        self.listeners
+            .borrow_mut()
+            .remove(&(String::from(event_type), capture))
+    }
+
+    pub fn insert(
+        &self,
+        event_type: String,
+        capture: bool,
+        options: VListenerOptions,
+        listener: DomEvent,
+    ) {
+        self.listeners
+            .borrow_mut()
+            .insert((event_type, capture), (options, listener));
+    }
+
+    /// Removes all listeners.
+    pub fn clear(&self) {
+        let listeners = std::mem::take(&mut *self.listeners.borrow_mut());
+        drop(listeners);
+    }
+}
+
+impl std::default::Default for VDirectListeners {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for VDirectListeners {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VDirectListeners({})", self.len())
+    }
+}
+
 impl std::fmt::Debug for VEventHandlers {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "VEventHandlers")
@@ -137,16 +401,91 @@ impl std::fmt::Debug for VEventHandlers {
 }
 
 impl<T> VEvent<T> {
-    pub fn new(data: T, stop_propagation: Rc<Cell<bool>>) -> Self {
+    pub fn new(
+        data: T,
+        stop_propagation: Rc<Cell<bool>>,
+        stop_immediate_propagation: Rc<Cell<bool>>,
+    ) -> Self {
         Self {
             data,
             stop_propagation,
+            stop_immediate_propagation,
+            source: None,
         }
     }
 
+    /// Reads the event through `source` instead of `data`.
+    pub(crate) fn with_source(mut self, source: Rc<dyn VEventSource>) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    fn map<U>(self, f: impl FnOnce(T) -> U) -> VEvent<U> {
+        VEvent {
+            data: f(self.data),
+            stop_propagation: self.stop_propagation,
+            stop_immediate_propagation: self.stop_immediate_propagation,
+            source: self.source,
+        }
+    }
+
+    /// The node which the event is dispatched to. Unlike `target`, it works without browser.
+    /// It is `None` if the event is not dispatched by `DomRenderer`.
+    pub fn event_target(&self) -> Option<VEventTarget> {
+        self.source.as_ref()?.target()
+    }
+
+    /// Stops handlers of ancestor elements.
+    /// This does not stop the native event. See `stop_native_propagation`.
     pub fn stop_propagation(&self) {
         self.stop_propagation.set(true);
     }
+
+    /// Stops handlers of ancestor elements and remaining handlers of the current element.
+    pub fn stop_immediate_propagation(&self) {
+        self.stop_propagation.set(true);
+        self.stop_immediate_propagation.set(true);
+    }
+}
+
+impl<T: AsRef<web_sys::Event>> VEvent<T> {
+    /// Cancels the default action of the browser, like form submission or link navigation.
+    pub fn prevent_default(&self) {
+        if let Some(source) = &self.source {
+            source.prevent_default();
+        } else {
+            self.data.as_ref().prevent_default();
+        }
+    }
+
+    pub fn default_prevented(&self) -> bool {
+        if let Some(source) = &self.source {
+            source.default_prevented()
+        } else {
+            self.data.as_ref().default_prevented()
+        }
+    }
+
+    /// Stops the native event at the root element of nusa.
+    /// Listeners which are added to descendant elements by other libraries are not called.
+    pub fn stop_native_propagation(&self) {
+        if let Some(source) = &self.source {
+            source.stop_propagation();
+        } else {
+            self.data.as_ref().stop_propagation();
+        }
+    }
+}
+
+impl VEvent<web_sys::KeyboardEvent> {
+    /// `key` of the event, which is read through `DomBackend`.
+    pub fn key(&self) -> String {
+        if let Some(source) = &self.source {
+            source.key().unwrap_or_default()
+        } else {
+            self.data.key()
+        }
+    }
 }
 
 impl<T: Clone> VEvent<T> {
@@ -156,12 +495,26 @@ impl<T: Clone> VEvent<T> {
 }
 
 impl<T: JsCast> VEvent<T> {
+    /// Whether the event is an instance of `U`. Every event of `MemoryDomBackend` is.
+    pub fn is_instance_of<U: JsCast>(&self) -> bool {
+        if let Some(source) = &self.source {
+            source.is_instance(&|e| e.has_type::<U>())
+        } else {
+            self.data.has_type::<U>()
+        }
+    }
+
     pub fn dyn_into<U: JsCast>(self) -> Result<VEvent<U>, Self> {
-        match self.data.dyn_into::<U>() {
-            Ok(data) => Ok(VEvent::new(data, self.stop_propagation)),
-            Err(data) => Err(Self::new(data, self.stop_propagation)),
+        if self.is_instance_of::<U>() {
+            Ok(self.unchecked_into())
+        } else {
+            Err(self)
         }
     }
+
+    pub fn unchecked_into<U: JsCast>(self) -> VEvent<U> {
+        self.map(|data| data.unchecked_into())
+    }
 }
 
 impl VReferHandler {
@@ -184,6 +537,82 @@ impl VReferHandler {
     }
 }
 
+impl VNodeRef {
+    pub(crate) fn new(
+        node: Rc<RefCell<Option<RefTarget>>>,
+        on_attach: Option<Box<dyn FnOnce(web_sys::Node) -> Option<Msg>>>,
+        on_detach: Option<Box<dyn FnOnce() -> Msg>>,
+    ) -> Self {
+        Self {
+            node,
+            on_attach,
+            on_detach: Rc::new(RefCell::new(on_detach)),
+        }
+    }
+
+    pub fn is_same_ref(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.node, &other.node)
+    }
+
+    fn is_attached<B: DomBackend>(&self, backend: &B, raw: &B::Node) -> bool {
+        self.node
+            .borrow()
+            .as_ref()
+            .and_then(|target| target.raw.downcast_ref::<B::Node>())
+            .is_some_and(|attached| backend.is_same_node(attached, raw))
+    }
+
+    /// Sets `raw`, and returns the handler of attach if it is another node.
+    /// The handler is not called by backends without `web_sys::Node`.
+    pub fn attach<B: DomBackend>(
+        &mut self,
+        backend: &B,
+        raw: &B::Node,
+    ) -> Option<Box<dyn FnOnce() -> Option<Msg>>> {
+        if self.is_attached(backend, raw) {
+            return None;
+        }
+
+        let r_node = backend.to_r_node(raw);
+        self.node.replace(Some(RefTarget {
+            raw: Box::new(raw.clone()),
+            r_node: r_node.clone(),
+        }));
+        let r_node = r_node?;
+        let on_attach = self.on_attach.take()?;
+        Some(Box::new(move || on_attach(r_node)))
+    }
+
+    /// Clears the reference if it is `raw`, and returns the handler of detach.
+    pub fn detach<B: DomBackend>(
+        &self,
+        backend: &B,
+        raw: &B::Node,
+    ) -> Option<Box<dyn FnOnce() -> Option<Msg>>> {
+        if !self.is_attached(backend, raw) {
+            return None;
+        }
+
+        self.node.replace(None);
+        let on_detach = self.on_detach.borrow_mut().take()?;
+        Some(Box::new(move || Some(on_detach())))
+    }
+
+    pub fn as_rendered(&self) -> Self {
+        Self {
+            node: Rc::clone(&self.node),
+            on_attach: None,
+            on_detach: Rc::clone(&self.on_detach),
+        }
+    }
+}
+
+impl std::fmt::Debug for VNodeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VNodeRef")
+    }
+}
+
 impl std::fmt::Debug for VReferHandler {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "VReferHandler")
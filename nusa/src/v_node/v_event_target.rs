@@ -0,0 +1,155 @@
+use crate::dom_backend::DomBackend;
+use std::any::Any;
+use std::rc::Rc;
+
+/// The target of `VEvent`, which is read through `DomBackend`.
+/// It works without browser, e.g. in `MemoryDomBackend`.
+#[derive(Clone)]
+pub struct VEventTarget {
+    node: Rc<dyn TargetSource>,
+}
+
+/// A dispatched event, which `VEvent` reads through `DomBackend`.
+pub(crate) trait VEventSource {
+    fn is_instance(&self, is_instance: &dyn Fn(&web_sys::Event) -> bool) -> bool;
+    fn target(&self) -> Option<VEventTarget>;
+    fn prevent_default(&self);
+    fn default_prevented(&self) -> bool;
+    fn stop_propagation(&self);
+    fn key(&self) -> Option<String>;
+}
+
+trait TargetSource {
+    fn raw(&self) -> &dyn Any;
+    fn r_node(&self) -> Option<web_sys::Node>;
+    fn tag_name(&self) -> Option<String>;
+    fn value(&self) -> Option<String>;
+    fn checked(&self) -> Option<bool>;
+    fn selected_values(&self) -> Option<Vec<String>>;
+    fn files(&self) -> Option<Vec<web_sys::File>>;
+    fn content_editable_text(&self) -> Option<String>;
+}
+
+pub(crate) struct BackendEvent<B: DomBackend> {
+    backend: B,
+    e: web_sys::Event,
+}
+
+struct BackendTarget<B: DomBackend> {
+    backend: B,
+    node: B::Node,
+}
+
+impl VEventTarget {
+    /// The target as a node of `B`, e.g. `MemoryNode`.
+    pub fn raw<B: DomBackend>(&self) -> Option<B::Node> {
+        self.node.raw().downcast_ref::<B::Node>().cloned()
+    }
+
+    /// The target, or `None` if the backend has no `web_sys::Node`.
+    pub fn r_node(&self) -> Option<web_sys::Node> {
+        self.node.r_node()
+    }
+
+    /// The local name of the target element, like `input`.
+    pub fn tag_name(&self) -> Option<String> {
+        self.node.tag_name()
+    }
+
+    /// The live `value` of form controls, or `value` attribute of other elements.
+    pub fn value(&self) -> Option<String> {
+        self.node.value()
+    }
+
+    /// `checked` of `input`.
+    pub fn checked(&self) -> Option<bool> {
+        self.node.checked()
+    }
+
+    /// Values of selected options of `select`.
+    pub fn selected_values(&self) -> Option<Vec<String>> {
+        self.node.selected_values()
+    }
+
+    /// Files of `input`.
+    pub fn files(&self) -> Option<Vec<web_sys::File>> {
+        self.node.files()
+    }
+
+    /// `innerText` of an element whose `contenteditable` is enabled.
+    pub fn content_editable_text(&self) -> Option<String> {
+        self.node.content_editable_text()
+    }
+}
+
+impl<B: DomBackend> BackendEvent<B> {
+    pub fn new(backend: B, e: web_sys::Event) -> Self {
+        Self { backend, e }
+    }
+}
+
+impl<B: DomBackend> VEventSource for BackendEvent<B> {
+    fn is_instance(&self, is_instance: &dyn Fn(&web_sys::Event) -> bool) -> bool {
+        self.backend.is_event_instance(&self.e, is_instance)
+    }
+
+    fn target(&self) -> Option<VEventTarget> {
+        let node = self.backend.event_target(&self.e)?;
+        Some(VEventTarget {
+            node: Rc::new(BackendTarget {
+                backend: self.backend.clone(),
+                node,
+            }),
+        })
+    }
+
+    fn prevent_default(&self) {
+        self.backend.prevent_default(&self.e);
+    }
+
+    fn default_prevented(&self) -> bool {
+        self.backend.default_prevented(&self.e)
+    }
+
+    fn stop_propagation(&self) {
+        self.backend.stop_propagation(&self.e);
+    }
+
+    fn key(&self) -> Option<String> {
+        self.backend.event_key(&self.e)
+    }
+}
+
+impl<B: DomBackend> TargetSource for BackendTarget<B> {
+    fn raw(&self) -> &dyn Any {
+        &self.node
+    }
+
+    fn r_node(&self) -> Option<web_sys::Node> {
+        self.backend.to_r_node(&self.node)
+    }
+
+    fn tag_name(&self) -> Option<String> {
+        self.backend.tag_name(&self.node)
+    }
+
+    fn value(&self) -> Option<String> {
+        self.backend.get_value(&self.node)
+    }
+
+    fn checked(&self) -> Option<bool> {
+        self.backend.get_checked(&self.node)
+    }
+
+    fn selected_values(&self) -> Option<Vec<String>> {
+        self.backend.selected_values(&self.node)
+    }
+
+    fn files(&self) -> Option<Vec<web_sys::File>> {
+        self.backend.files(&self.node)
+    }
+
+    fn content_editable_text(&self) -> Option<String> {
+        self.backend.content_editable_text(&self.node)
+    }
+}
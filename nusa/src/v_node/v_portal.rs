@@ -0,0 +1,31 @@
+use super::VNode;
+use crate::html::PortalTarget;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Children which are rendered into `target` instead of the parent.
+pub struct VPortal {
+    pub target: PortalTarget,
+    pub children: VecDeque<VNode>,
+    /// An id which `DomRenderer` gives to the portal. It is shared with the rendered copy.
+    pub id: Rc<Cell<Option<usize>>>,
+}
+
+impl VPortal {
+    /// Children are not copied, because the renderer of the portal keeps them.
+    pub fn as_rendered(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+            children: VecDeque::new(),
+            id: Rc::clone(&self.id),
+        }
+    }
+}
+
+impl std::fmt::Debug for VPortal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[Portal]")?;
+        f.debug_list().entries(self.children.iter()).finish()
+    }
+}